@@ -0,0 +1,495 @@
+//! Proc-macro front end for `biject_into`, used by `biject_into::checked::bijection!`
+//! (behind the `proc` feature).
+//!
+//! The branches are only validated here, so that errors can point at the offending tokens.
+//! The expansion itself is still left to the declarative `bijection!` macro, which gets the input as-is.
+//!
+//! Also provides the extension traits of `#[ext]`, as declarative macros can't derive new identifiers.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+
+/// Validates the bijection branches, then expands to `biject_into::bijection!` with the same input.
+///
+/// Expects the path of `biject_into` in parentheses before the input (`($crate)`, from `checked::bijection!`),
+/// which keeps working when the dependency is renamed.
+/// See the docs of `biject_into::checked` for details.
+#[proc_macro]
+pub fn bijection(input: TokenStream) -> TokenStream {
+    let mut tokens = TokenStream2::from(input).into_iter();
+    let krate = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream()
+        }
+        _ => {
+            return quote!(::core::compile_error!("Expected the path of biject_into first");)
+                .into();
+        }
+    };
+    let input: TokenStream2 = tokens.collect();
+    match validate(input.clone()) {
+        Ok(()) => quote!(#krate::bijection!(#input);).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Generates the extension trait for one direction of `bijection!(#[ext] ...)`. Not public API.
+///
+/// Expects `[vis] from Src => Dst` or `[vis] try (Error) Src => Dst`, and generates `SrcExt`
+/// with a `to_dst` (or `try_to_dst`) method, calling the `From` (or `TryFrom`) impl.
+#[doc(hidden)]
+#[proc_macro]
+pub fn ext(input: TokenStream) -> TokenStream {
+    match syn::parse::<ExtInput>(input) {
+        Ok(input) => input.expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct ExtInput {
+    vis: syn::Visibility,
+    /// The error type of a fallible conversion
+    error: Option<syn::Type>,
+    src: syn::Type,
+    dst: syn::Type,
+}
+
+impl syn::parse::Parse for ExtInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let vis;
+        syn::bracketed!(vis in input);
+        let vis = vis.parse()?;
+        let error = if input.peek(syn::Token![try]) {
+            input.parse::<syn::Token![try]>()?;
+            let error;
+            syn::parenthesized!(error in input);
+            Some(error.parse()?)
+        } else {
+            let kind: syn::Ident = input.parse()?;
+            if kind != "from" {
+                return Err(syn::Error::new(kind.span(), "expected `from` or `try`"));
+            }
+            None
+        };
+        let src = input.parse()?;
+        input.parse::<syn::Token![=>]>()?;
+        let dst = input.parse()?;
+        Ok(ExtInput {
+            vis,
+            error,
+            src,
+            dst,
+        })
+    }
+}
+
+impl ExtInput {
+    fn expand(&self) -> TokenStream2 {
+        let (src_name, dst_name) = match (type_name(&self.src), type_name(&self.dst)) {
+            (Ok(src_name), Ok(dst_name)) => (src_name, dst_name),
+            (Err(error), Ok(_)) | (Ok(_), Err(error)) => return error.to_compile_error(),
+            (Err(mut error), Err(dst_error)) => {
+                error.combine(dst_error);
+                return error.to_compile_error();
+            }
+        };
+
+        let ExtInput {
+            vis,
+            error,
+            src,
+            dst,
+        } = self;
+        let trait_name = syn::Ident::new(
+            &format!("{}Ext", upper_first(&src_name.to_string())),
+            src_name.span(),
+        );
+        let snake = to_snake_case(&dst_name.to_string());
+        let trait_doc = format!("Conversion methods for `{src_name}`, generated by `bijection!`");
+        match error {
+            None => {
+                let method = syn::Ident::new(&format!("to_{snake}"), dst_name.span());
+                let method_doc = format!("Converts into `{dst_name}`, using its `From` impl");
+                quote! {
+                    #[doc = #trait_doc]
+                    #vis trait #trait_name {
+                        #[doc = #method_doc]
+                        fn #method(self) -> #dst;
+                    }
+
+                    #[automatically_derived]
+                    impl #trait_name for #src {
+                        #[inline]
+                        fn #method(self) -> #dst {
+                            ::core::convert::From::from(self)
+                        }
+                    }
+                }
+            }
+            Some(error) => {
+                let method = syn::Ident::new(&format!("try_to_{snake}"), dst_name.span());
+                let method_doc =
+                    format!("Tries to convert into `{dst_name}`, using its `TryFrom` impl");
+                quote! {
+                    #[doc = #trait_doc]
+                    #vis trait #trait_name {
+                        #[doc = #method_doc]
+                        fn #method(self) -> ::core::result::Result<#dst, #error>;
+                    }
+
+                    #[automatically_derived]
+                    impl #trait_name for #src {
+                        #[inline]
+                        fn #method(self) -> ::core::result::Result<#dst, #error> {
+                            ::core::convert::TryFrom::try_from(self)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The last path segment of a type, which names its extension trait and the methods converting into it.
+fn type_name(ty: &syn::Type) -> syn::Result<syn::Ident> {
+    match ty {
+        // Types passed through a declarative macro come wrapped in an invisible group
+        syn::Type::Group(group) => type_name(&group.elem),
+        syn::Type::Paren(paren) => type_name(&paren.elem),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last().expect("paths are never empty");
+            Ok(syn::Ident::new(
+                segment.ident.to_string().trim_start_matches("r#"),
+                segment.ident.span(),
+            ))
+        }
+        _ => Err(syn::Error::new_spanned(
+            ty,
+            "#[ext] can only name methods after a path type (e.g. `Foo` or `std::time::Duration`)",
+        )),
+    }
+}
+
+/// Ex: `HttpStatus` and `HTTPStatus` both become `http_status`, `u8` stays as-is
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    snake
+}
+
+fn upper_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A single `pattern => pattern` branch, with an optional explicit reverse.
+struct Branch {
+    /// Branches with attributes (e.g. `#[cfg(...)]`) may not be compiled at all
+    has_attrs: bool,
+    lhs: Vec<TokenTree>,
+    rhs: Vec<TokenTree>,
+    reverse: Option<(Vec<TokenTree>, Vec<TokenTree>)>,
+}
+
+impl Branch {
+    /// The pattern of the reverse `match`
+    fn reverse_pattern(&self) -> &[TokenTree] {
+        match &self.reverse {
+            Some((pattern, _)) => pattern,
+            None => &self.rhs,
+        }
+    }
+}
+
+fn validate(input: TokenStream2) -> syn::Result<()> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    // The declaration block is the first brace group, anything before it declares the types.
+    // Anything unexpected is left to the declarative macro, which has its own (less precise) errors.
+    let Some((block_index, block)) =
+        tokens
+            .iter()
+            .enumerate()
+            .find_map(|(index, token)| match token {
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    Some((index, group))
+                }
+                _ => None,
+            })
+    else {
+        return Ok(());
+    };
+    // One-directional conversions use the branches as a plain `match`, so there is nothing to check
+    let types = &tokens[..block_index];
+    if find_fat_arrow(types).is_some() || find_reverse_arrow(types).is_some() {
+        return Ok(());
+    }
+    let Some(branches) = parse_branches(block.stream().into_iter().collect()) else {
+        return Ok(());
+    };
+
+    let mut errors = Errors::default();
+    for branch in &branches {
+        if branch.reverse.is_none() {
+            for side in [&branch.lhs, &branch.rhs] {
+                if let Some(span) = find_or(side) {
+                    errors.push(syn::Error::new(
+                        span,
+                        "Or-patterns are not supported in bijection branches, as the reverse side would be ambiguous",
+                    ));
+                }
+                if let Some(span) = find_rest(side) {
+                    errors.push(syn::Error::new(
+                        span,
+                        "Rest patterns (`..`) can't be read as an expression, give the branch an explicit reverse instead",
+                    ));
+                }
+                if let Some(span) = find_discard(side) {
+                    errors.push(syn::Error::new(
+                        span,
+                        "A discarded field (`_`) can't be read as an expression, give the branch an explicit reverse which fills it in instead",
+                    ));
+                }
+            }
+        }
+    }
+
+    // Like in the declarative macro, this is a textual comparison
+    let mut seen: Vec<(String, &Branch)> = Vec::new();
+    for branch in branches.iter().filter(|branch| !branch.has_attrs) {
+        let pattern = branch.reverse_pattern();
+        let key = tokens_to_string(pattern);
+        if let Some((_, first)) = seen.iter().find(|(seen_key, _)| *seen_key == key) {
+            let mut error = syn::Error::new_spanned(
+                to_stream(pattern),
+                format!(
+                    "Duplicate right-hand side in bijection branches: `{key}` is already mapped"
+                ),
+            );
+            error.combine(syn::Error::new_spanned(
+                to_stream(first.reverse_pattern()),
+                "first mapped here",
+            ));
+            errors.push(error);
+        } else {
+            seen.push((key, branch));
+        }
+    }
+
+    errors.finish()
+}
+
+/// Splits the declaration block into branches, or returns `None` if it doesn't follow the grammar.
+fn parse_branches(tokens: Vec<TokenTree>) -> Option<Vec<Branch>> {
+    let mut branches = Vec::new();
+    let mut rest = &tokens[..];
+    while !rest.is_empty() {
+        let mut has_attrs = false;
+        while let [TokenTree::Punct(hash), group, tail @ ..] = rest
+            && hash.as_char() == '#'
+            && is_group(group, Delimiter::Bracket)
+        {
+            has_attrs = true;
+            rest = tail;
+        }
+
+        // Ex: forward_only { Foo::C | Foo::D => Bar::X }, these are only read as a `match`, so there's nothing to check
+        if let [TokenTree::Ident(ident), group, tail @ ..] = rest
+            && ident == "forward_only"
+            && is_group(group, Delimiter::Brace)
+        {
+            rest = match tail {
+                [TokenTree::Punct(punct), after @ ..] if matches!(punct.as_char(), ',' | ';') => {
+                    after
+                }
+                _ => tail,
+            };
+            continue;
+        }
+
+        let (lhs, tail) = split_pattern(rest)?;
+        let (rhs, tail) = split_expr(tail);
+        rest = tail;
+
+        // Ex: Foo(x) => Bar(f(x)) <=> g, only the part before the inverse function is a pattern
+        let rhs = match find_spaceship(&rhs) {
+            Some(index) => rhs[..index].to_vec(),
+            None => rhs,
+        };
+
+        let mut reverse = None;
+        match rest {
+            [TokenTree::Punct(punct), tail @ ..] if punct.as_char() == ',' => rest = tail,
+            [TokenTree::Punct(punct), tail @ ..] if punct.as_char() == ';' => {
+                rest = tail;
+                if let [TokenTree::Ident(ident), tail @ ..] = rest
+                    && ident == "reverse"
+                {
+                    let (pattern, tail) = split_pattern(tail)?;
+                    let (expr, tail) = split_expr(tail);
+                    reverse = Some((pattern, expr));
+                    rest = match tail {
+                        [separator, after @ ..]
+                            if is_punct(separator, ',') || is_punct(separator, ';') =>
+                        {
+                            after
+                        }
+                        _ => tail,
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        branches.push(Branch {
+            has_attrs,
+            lhs,
+            rhs,
+            reverse,
+        });
+    }
+    Some(branches)
+}
+
+/// Takes the tokens up to the next `=>`, and skips it.
+fn split_pattern(tokens: &[TokenTree]) -> Option<(Vec<TokenTree>, &[TokenTree])> {
+    let index = find_fat_arrow(tokens)?;
+    Some((tokens[..index].to_vec(), &tokens[index + 2..]))
+}
+
+/// Takes the tokens up to the next separator, or a single block (which needs no separator, like in a `match`).
+fn split_expr(tokens: &[TokenTree]) -> (Vec<TokenTree>, &[TokenTree]) {
+    if let [block, tail @ ..] = tokens
+        && is_group(block, Delimiter::Brace)
+    {
+        return (vec![block.clone()], tail);
+    }
+    let index = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Punct(punct) if matches!(punct.as_char(), ',' | ';')))
+        .unwrap_or(tokens.len());
+    (tokens[..index].to_vec(), &tokens[index..])
+}
+
+/// Finds a `=>` on the top level, which is lexed as two joint puncts (and isn't part of `<=>`).
+fn find_fat_arrow(tokens: &[TokenTree]) -> Option<usize> {
+    (0..tokens.len().saturating_sub(1)).find(|&i| {
+        is_joint(&tokens[i], '=')
+            && is_punct(&tokens[i + 1], '>')
+            && (i == 0 || !is_joint(&tokens[i - 1], '<'))
+    })
+}
+
+/// Finds a `<=` on the top level, which isn't part of a `<=>`.
+fn find_reverse_arrow(tokens: &[TokenTree]) -> Option<usize> {
+    (0..tokens.len().saturating_sub(1)).find(|&i| {
+        is_joint(&tokens[i], '<') && is_punct(&tokens[i + 1], '=') && !is_joint(&tokens[i + 1], '=')
+    })
+}
+
+/// Finds a `<=>` on the top level.
+fn find_spaceship(tokens: &[TokenTree]) -> Option<usize> {
+    (0..tokens.len().saturating_sub(2)).find(|&i| {
+        is_joint(&tokens[i], '<') && is_joint(&tokens[i + 1], '=') && is_punct(&tokens[i + 2], '>')
+    })
+}
+
+/// Finds a `|` anywhere in the tokens, including nested groups.
+fn find_or(tokens: &[TokenTree]) -> Option<Span> {
+    tokens.iter().find_map(|token| match token {
+        TokenTree::Punct(punct) if punct.as_char() == '|' => Some(punct.span()),
+        TokenTree::Group(group) => find_or(&group.stream().into_iter().collect::<Vec<_>>()),
+        _ => None,
+    })
+}
+
+/// Finds a rest pattern `..` anywhere in the tokens, except as a subpattern (e.g. `rest @ ..`).
+fn find_rest(tokens: &[TokenTree]) -> Option<Span> {
+    tokens
+        .iter()
+        .enumerate()
+        .find_map(|(i, token)| match token {
+            TokenTree::Punct(punct)
+                if is_joint(token, '.')
+                    && tokens
+                        .get(i + 1)
+                        .is_some_and(|next| is_punct(next, '.') && !is_joint(next, '.'))
+                    && (i == 0 || !is_punct(&tokens[i - 1], '@')) =>
+            {
+                Some(punct.span())
+            }
+            TokenTree::Group(group) => find_rest(&group.stream().into_iter().collect::<Vec<_>>()),
+            _ => None,
+        })
+}
+
+/// Finds a `_` nested in a pattern (e.g. `Foo::Unknown(_)`), as a whole `_` side is a wildcard branch instead.
+fn find_discard(tokens: &[TokenTree]) -> Option<Span> {
+    tokens.iter().find_map(|token| match token {
+        TokenTree::Group(group) => find_underscore(&group.stream().into_iter().collect::<Vec<_>>()),
+        _ => None,
+    })
+}
+
+/// Finds a `_` anywhere in the tokens, including nested groups.
+fn find_underscore(tokens: &[TokenTree]) -> Option<Span> {
+    tokens.iter().find_map(|token| match token {
+        TokenTree::Ident(ident) if ident == "_" => Some(ident.span()),
+        TokenTree::Group(group) => find_underscore(&group.stream().into_iter().collect::<Vec<_>>()),
+        _ => None,
+    })
+}
+
+fn is_group(token: &TokenTree, delimiter: Delimiter) -> bool {
+    matches!(token, TokenTree::Group(group) if group.delimiter() == delimiter)
+}
+
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch)
+}
+
+fn is_joint(token: &TokenTree, ch: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch && punct.spacing() == Spacing::Joint)
+}
+
+fn to_stream(tokens: &[TokenTree]) -> TokenStream2 {
+    tokens.iter().cloned().collect()
+}
+
+fn tokens_to_string(tokens: &[TokenTree]) -> String {
+    to_stream(tokens).to_string()
+}
+
+/// Collects all errors, so each one is reported at once.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
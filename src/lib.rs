@@ -1,5 +1,8 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 /// Generates `From` impls for any two types, providing conversions between them.
 /// This is effectively a shorthand for creating two duplicate `match` statements
 /// with the sides swapped.
@@ -7,6 +10,8 @@
 /// This is especially useful for quickly mapping between two enums,
 /// but this works for any two types and any patterns.
 ///
+/// The impls are marked `#[automatically_derived]`, like the output of `#[derive]`.
+///
 /// # Usage
 /// ```text
 /// bijection!(Foo, Bar, {
@@ -20,6 +25,13 @@
 /// Because of the two-way nature of bijection, both sides must be valid patterns (without alternates)
 /// and expressions - that is, both `Foo::A => Bar::X` and `Bar::X => Foo::A` must be valid in a match expression.
 ///
+/// Branches are separated by either `,` or `;`, and a branch ending with a block needs no separator, like in a `match`.
+/// Comma-separated branches are processed in a single step, so large bijections should stick to commas.
+///
+/// A branch may also be written with `<=>` instead of `=>` (e.g. `Foo::A <=> Bar::X`), to spell out that it's read both ways.
+/// The two arrows are interchangeable, but a `<=>` branch is rejected outright if its left-hand side
+/// isn't a plain path (with an optional group) or literal, such as a wildcard or an or-pattern.
+///
 /// # Examples
 /// ```rust
 /// use biject_into::bijection;
@@ -78,474 +90,9500 @@
 /// assert_eq!(Option::<bool>::from(Tristate::Negative), Some(false));
 /// ```
 ///
-/// # Caveats
+/// Tuples (and arrays) work as either side as well, so a struct can be packed into a tuple and destructured back:
+/// ```rust
+/// use biject_into::bijection;
 ///
-/// ## Unreachable patterns
-/// By itself, the macro does not enforce bijection
+/// #[derive(Debug, PartialEq)]
+/// struct Rgb {
+///     r: u8,
+///     g: u8,
+///     b: u8,
+/// }
+///
+/// bijection!(Rgb, (u8, u8, u8), {
+///     Rgb { r, g, b } => (r, g, b),
+/// });
+///
+/// assert_eq!(<(u8, u8, u8)>::from(Rgb { r: 255, g: 128, b: 0 }), (255, 128, 0));
+/// assert_eq!(Rgb::from((255, 128, 0)), Rgb { r: 255, g: 128, b: 0 });
+/// ```
+///
+/// # Bindings
+/// A pattern may bind a value with a subpattern (e.g. `n @ 1..=9`).
+/// When that side is read as an expression, each `name @ subpattern` becomes just `name`,
+/// so the reverse of `Foo::Num(n @ 1..=9) => Bar::Digit(n)` is `Bar::Digit(n) => Foo::Num(n)`.
+/// The subpattern reaches up to the next comma, and is not checked in the reverse direction.
 /// ```rust
-/// # use biject_into::bijection;
-/// # #[derive(Debug, PartialEq, Clone)]
-/// # struct Foo(i32);
+/// use biject_into::bijection;
 ///
-/// # #[derive(Debug, PartialEq, Clone)]
-/// # struct Bar(i32);
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     Num(u8),
+/// }
 ///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     Digit(u8),
+///     Number(u8),
+/// }
 ///
 /// bijection!(Foo, Bar, {
-///     Foo(0) => Bar(0),
-///     Foo(1) => Bar(0), // Bar(0) is unreachable!
-///     Foo(1) => Bar(1), // Foo(1) is unreachable!
-///     Foo(x) => Bar(x),
+///     Foo::Num(n @ 0..=9) => Bar::Digit(n),
+///     Foo::Num(n) => Bar::Number(n),
 /// });
+///
+/// assert_eq!(Bar::from(Foo::Num(7)), Bar::Digit(7));
+/// assert_eq!(Bar::from(Foo::Num(42)), Bar::Number(42));
+/// assert_eq!(Foo::from(Bar::Digit(7)), Foo::Num(7));
 /// ```
-/// Luckily, this will still cause the macro to emit `unreachable_patterns` warnings,
-/// and even highlight the problematic patterns for you.
 ///
-/// You may wrap the macro in a block (or a module) and annotate it with `#[deny(unreachable_patterns)]`.
+/// A `ref` binding (or `ref mut`) is bound by reference, so it is cloned when that side is read as an expression:
+/// `ref s` becomes `Clone::clone(s)`, and a field shorthand like `Foo { ref s }` becomes `Foo { s: Clone::clone(s) }`.
+/// Written on both sides, this lets [`#[by_ref]`](#by_ref) (or a borrowed side) convert fields which aren't `Copy`.
+/// ```rust
+/// use biject_into::bijection;
 ///
-/// ## Bijection branches
-/// The bijection branches are structured to look like `match` branches, but unlike the latter,
-/// or-patterns (or any ambiguous patterns) are disallowed.
-/// ```rust,compile_fail
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     Name(String),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     Label { text: String },
+/// }
+///
+/// bijection!(#[by_ref] Foo, Bar, {
+///     Foo::Name(ref s) => Bar::Label { text: ref s },
+/// });
+///
+/// let bar = Bar::Label { text: "name".to_string() };
+/// assert_eq!(Foo::from(&bar), Foo::Name("name".to_string()));
+/// assert_eq!(Bar::from(Foo::Name("name".to_string())), bar);
+/// ```
+///
+/// # Branch attributes
+/// Attributes on a branch are copied onto both of its generated `match` arms.
+/// This is mostly useful for `#[cfg(...)]`, e.g. for variants which only exist with some feature enabled:
+/// ```text
+/// bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     #[cfg(feature = "extra")]
+///     Foo::E => Bar::E,
+/// });
+/// ```
+///
+/// ## Branch docs
+/// Doc comments on branches are collected into a mapping table, documented on each generated impl
+/// (after any [`#[doc]`](#doc) flags). Each doc line becomes a list item with both patterns,
+/// so `/// add op` on `Op::Add => Code::Add` shows up as "add op: `Op::Add` ↔ `Code::Add`".
+/// For branches with an explicit reverse, the reverse pattern is shown on the right.
+/// Undocumented branches are left out, and documented ones are listed even if a `#[cfg(...)]` removes them.
+/// This applies to the `From` and `TryFrom` impls; other conversions (like [`bijection_methods!`]) ignore the docs.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// pub enum Op {
+///     Add,
+///     Sub,
+/// }
+///
+/// pub enum Code {
+///     Add,
+///     Sub,
+/// }
+///
+/// bijection!(#[doc = "Maps operations to their wire codes."] Op, Code, {
+///     /// add op
+///     Op::Add => Code::Add,
+///     /// sub op
+///     Op::Sub => Code::Sub,
+/// });
+/// ```
+///
+/// # Explicit reverse branches
+/// A branch may supply its reverse separately, for cases where one side cannot be read the other way
+/// (e.g. the forward expression is a block or a method call like `Bar::builder().x(x).build()`, or the pattern binds something that it then discards).
+/// ```text
+/// <forward pattern> => <forward expression>; reverse <reverse pattern> => <reverse expression>
+/// ```
+/// The forward part only goes into the forward `match`, and the reverse part only into the reverse one.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Foo {
+///     Half(i32),
+///     Zero,
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Bar {
+///     Double(i32),
+///     Zero,
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::Half(n) => {
+///         let doubled = n * 4;
+///         Bar::Double(doubled)
+///     }; reverse Bar::Double(n) => Foo::Half(n / 4),
+///     Foo::Zero => Bar::Zero,
+/// });
+///
+/// assert_eq!(Bar::from(Foo::Half(3)), Bar::Double(12));
+/// assert_eq!(Foo::from(Bar::Double(12)), Foo::Half(3));
+/// ```
+/// The forward pattern may also have a guard, which is left out of the reverse `match`.
+/// A guard can't be reversed automatically, so guarded branches always need an explicit reverse.
+/// ```rust
 /// # use biject_into::bijection;
-/// # #[derive(Debug, PartialEq, Clone)]
-/// # struct Foo(i32);
+/// # #[derive(Debug, PartialEq)]
+/// # enum Foo { Val(i32) }
+/// # #[derive(Debug, PartialEq)]
+/// # enum Bar { Positive(i32), Other(i32) }
+/// bijection!(Foo, Bar, {
+///     Foo::Val(n) if n > 0 => Bar::Positive(n); reverse Bar::Positive(n) => Foo::Val(n),
+///     Foo::Val(n) => Bar::Other(n),
+/// });
 ///
-/// # #[derive(Debug, PartialEq, Clone)]
-/// # struct Bar(i32);
+/// assert_eq!(Bar::from(Foo::Val(3)), Bar::Positive(3));
+/// assert_eq!(Bar::from(Foo::Val(-3)), Bar::Other(-3));
+/// ```
+///
+/// ## Recursive types
+/// The branches may use the impls being generated, so the children of recursive types convert themselves,
+/// e.g. `Tree::Node(l, r) => Other::Node(Box::new((*l).into()), Box::new((*r).into()))`.
+/// Such a branch needs an explicit reverse doing the same, as a method call isn't a pattern.
+/// With [`#[boxed]`](#boxed) and [`#[auto_into]`](#auto_into), the boxed children are converted like any other field instead:
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tree {
+///     Leaf(i32),
+///     Node(Box<Tree>, Box<Tree>),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Other {
+///     Leaf(i32),
+///     Node(Box<Other>, Box<Other>),
+/// }
+///
+/// bijection!(#[boxed] #[auto_into] Tree, Other, {
+///     Tree::Leaf(x) => Other::Leaf(x),
+///     Tree::Node(l, r) => Other::Node(l, r),
+/// });
+///
+/// let tree = Tree::Node(Box::new(Tree::Leaf(1)), Box::new(Tree::Leaf(2)));
+/// assert_eq!(Other::from(tree), Other::Node(Box::new(Other::Leaf(1)), Box::new(Other::Leaf(2))));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// ## Rest patterns
+/// Rest patterns (`..`) skip the remaining fields, which then can't be filled in again by reading the pattern in reverse.
+/// Branches using them need an explicit reverse, which constructs the value some other way
+/// (such as with struct update syntax), and are rejected otherwise.
+/// ```rust
+/// # use biject_into::bijection;
+/// #[derive(Debug, PartialEq, Default)]
+/// struct Foo {
+///     x: u8,
+///     y: u8,
+///     z: u8,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X(u8),
+/// }
 ///
 /// bijection!(Foo, Bar, {
-///     // No or-pattern! That would be like writing Bar(0) => Foo(0) | Foo(1)
-///     Foo(0) | Foo(1) => Bar(0),
-///     // Inner or-patterns currently pass compilation,
-///     // and unexpectedly produce a bitwise or instead!
-///     Foo(2 | 3) => Bar(1),
-///     Foo(x) => Bar(x),
+///     Foo { x, .. } => Bar::X(x); reverse Bar::X(x) => Foo { x, ..Default::default() },
 /// });
+///
+/// assert_eq!(Bar::from(Foo { x: 1, y: 2, z: 3 }), Bar::X(1));
+/// assert_eq!(Foo::from(Bar::X(1)), Foo { x: 1, y: 0, z: 0 });
 /// ```
-/// Using an inner or-pattern may currently compile successfully, but it (incorrectly) produces
-/// a bitwise or instead.
-#[macro_export]
-macro_rules! bijection {
-    // Final construction of the From impls
-    (@
-    ($first_ty:ty, $second_ty:ty)
-        { $($first_done:tt)* }
-        { $($second_done:tt)* }
-        ()
-        ()
-    ) => {
-        impl From<$first_ty> for $second_ty {
-            fn from(value: $first_ty) -> Self {
-                match value {
-                    $($first_done)*
-                }
-            }
-        }
-
-        impl From<$second_ty> for $first_ty {
-            fn from(value: $second_ty) -> Self {
-                match value {
-                    $($second_done)*
-                }
-            }
-        }
-    };
-
-    // Entry
-    ($first_ty:ty, $second_ty:ty,
-        {$($bij:tt)*}
-    ) => {
-        bijection!(@
-            ($first_ty, $second_ty)
-            {}
-            {}
-            // Double up the bijection statements for matching
-            ($($bij)*)
-            ($($bij)*)
-        );
-    };
-
-    // Normalize by munching rules sequentially
-    // This matches the initial $($bij)* with two macro patterns at once
-    (@
-    ($first_ty:ty, $second_ty:ty)
-        { $($first_done:tt)* }
-        { $($second_done:tt)* }
-        ($first_pat:pat_param => $first_expr:expr      , $($first_rest:tt )*)
-        ($second_expr:expr    => $second_pat:pat_param , $($second_rest:tt)*)
-    ) => {
-        bijection!(@
-            ($first_ty, $second_ty)
-            {
-                $($first_done)*
-                $first_pat => $first_expr,
-            }
-            {
-                $($second_done)*
-                $second_pat => $second_expr,
-            }
-            ($($first_rest)*)
-            ($($second_rest)*)
-        );
-    };
-
-    // Normalization without the trailing comma
-    (@
-    ($first_ty:ty, $second_ty:ty)
-        { $($first_done:tt)* }
-        { $($second_done:tt)* }
-        ($first_pat:pat_param => $first_expr:expr     )
-        ($second_expr:expr    => $second_pat:pat_param)
-    ) => {
-        bijection!(@
-            ($first_ty, $second_ty)
-            {
-                $($first_done)*
-                $first_pat => $first_expr,
-            }
-            {
-                $($second_done)*
-                $second_pat => $second_expr,
-            }
-            ()
-            ()
-        );
-    };
-
-    // ===== Invalid patterns for better compiler errors =====
-
-    // Notes:
-    // - Using the matched type tokens somehow helps with highlighting (at least in RustRover).
-    //   This is done via loose `let` declarations.
-    // - Similarly for other tokens
-
-    // Internal macro errors
-
-    // Invalid bijection match statements (e.g. Foo::A = Bar::X)
-    (@
-    ($first_ty:ty, $second_ty:ty)
-        { $($first_done:tt)* }
-        { $($second_done:tt)* }
-        ($($first_rest:tt )*)
-        ($($second_rest:tt)*)
-    ) => {
-        {
-            let _: $first_ty;
-            let _: $second_ty;
-            // This match statement might produce a better (native) compiler error message
-            // Example: `Foo::A = Bar::X` will make it complain about needing `=>` instead
-            // The #allow suppresses an unnecessary lint
-            #[allow(unreachable_code)]
-            match unreachable!() {
-                $($first_rest)*
-            };
-            compile_error!(concat!("Invalid bijection pattern:\n", stringify!($($first_rest)*)));
-        }
-    };
-
-    // Fallback
-    (@ $($unknown:tt)*) => {
-        {
-            // Uncomment stringify and comment out the compiler error for debugging
-            // const _: &str = concat!($(stringify!($unknown)),*);
-            compile_error!("Uncaught internal macro error");
-        }
-    };
-
-    // Incorrect syntax
-
-    // Ex: bijection!(Foo, Bar)
-    ($first_ty:ty, $second_ty:ty $(,)?) => {
-        {
-            let _: $first_ty;
-            let _: $second_ty;
-            compile_error!("Missing bijection declaration block after types");
-        }
-    };
-
-    // Ex: bijection!(Foo, Bar {})
-    ($first_ty:ty, $second_ty:tt $bij:block) => {
-        {
-            let _: $first_ty;
-            let _: $second_ty;
-            compile_error!("Bijection declaration block must be separated with a comma");
-        }
-    };
-
-    // Ex: bijection!(Foo, Bar, Foo::A => Bar::X)
-    ($first_ty:ty, $second_ty:tt, $($bij:tt)+) => {
-        {
-            let _: $first_ty;
-            let _: $second_ty;
-            compile_error!(
-                concat!(
-                    "Bijection declaration block expected (got: ",
-                    stringify!($($bij)+),
-                    ")"
-                )
-            );
-        }
-    };
-
-    // Same as the above without the comma
-    // Ex: bijection!(Foo, Bar Foo::A => Bar::X)
-    ($first_ty:ty, $second_ty:tt $($bij:tt)+) => {
-        {
-            let _: $first_ty;
-            let _: $second_ty;
-            compile_error!(
-                concat!(
-                    "Bijection declaration block expected (got: ",
-                    stringify!($($bij)+),
-                    ")"
-                )
-            );
-        }
-    };
-
-    // Ex: bijection!(Foo, { Foo::A => Bar::X })
-    ($first_ty:ty $(, $($bij:tt)*)?) => {
-        {
-            let _: $first_ty;
-            compile_error!("Missing second type");
-        }
-    };
+///
+/// ## Discarded fields
+/// The same goes for a `_` inside a pattern, which discards a field: `Foo::Unknown(_)` isn't an expression.
+/// The explicit reverse then has to make up the field, e.g. with its default:
+/// ```rust
+/// # use biject_into::bijection;
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     Known(u8),
+///     Unknown(u32),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     Known(u8),
+///     Unknown,
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::Known(n) => Bar::Known(n),
+///     Foo::Unknown(_) => Bar::Unknown; reverse Bar::Unknown => Foo::Unknown(Default::default()),
+/// });
+///
+/// assert_eq!(Bar::from(Foo::Unknown(404)), Bar::Unknown);
+/// assert_eq!(Foo::from(Bar::Unknown), Foo::Unknown(0));
+/// ```
+///
+/// ## Inverse functions
+/// When the only difference is a function applied to a single field, the reverse can be given as just its inverse,
+/// instead of a whole explicit reverse:
+/// ```text
+/// <pattern> => <path>(<function>(<binding>)) <=> <inverse function or closure>
+/// ```
+/// The reverse `match` then binds the field on its own (`Bar::Millimeters(m)`), and passes it through the inverse function
+/// before building the first side. The functions must actually be inverses of each other for the values that occur.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Length {
+///     Meters(u32),
+///     Unknown,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Size {
+///     Millimeters(u32),
+///     Unknown,
+/// }
+///
+/// fn meters_to_mm(m: u32) -> u32 {
+///     m * 1000
+/// }
+///
+/// fn mm_to_meters(mm: u32) -> u32 {
+///     mm / 1000
+/// }
+///
+/// bijection!(Length, Size, {
+///     Length::Meters(m) => Size::Millimeters(meters_to_mm(m)) <=> mm_to_meters,
+///     Length::Unknown => Size::Unknown,
+/// });
+///
+/// assert_eq!(Size::from(Length::Meters(2)), Size::Millimeters(2000));
+/// assert_eq!(Length::from(Size::Millimeters(2000)), Length::Meters(2));
+/// ```
+///
+/// The inverse can also be a closure over the field, optionally with its type (`|d: Duration| ...`).
+/// Its body is inlined into the reverse branch, so it's fine for it to be a method call:
+/// ```rust
+/// use biject_into::bijection;
+/// use core::time::Duration;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     Timestamp(u64),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     Time(Duration),
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::Timestamp(secs) => Bar::Time(Duration::from_secs(secs)) <=> |d| d.as_secs(),
+/// });
+///
+/// assert_eq!(Bar::from(Foo::Timestamp(60)), Bar::Time(Duration::from_secs(60)));
+/// assert_eq!(Foo::from(Bar::Time(Duration::from_secs(60))), Foo::Timestamp(60));
+/// ```
+///
+/// A whole value can also be cast with `as`, with the cast back as the inverse: `n => n as u8 <=> |x| x as i8`.
+/// This reinterprets the bits of an integer (`-1` is `255`), which round-trips between types of the same width.
+/// Both types are primitives then, and `From<i8> for u8` would be a foreign impl, so this needs a local trait:
+/// ```rust
+/// use biject_into::bijection_trait;
+///
+/// trait Reinterpret<T> {
+///     fn convert(self) -> T;
+/// }
+///
+/// bijection_trait!(Reinterpret, i8, u8, {
+///     n => n as u8 <=> |x| x as i8,
+/// });
+///
+/// assert_eq!(Reinterpret::<u8>::convert(-1i8), 255);
+/// assert_eq!(Reinterpret::<i8>::convert(128u8), -128);
+/// ```
+///
+/// ## Field casts
+/// A binding on the left-hand side can be given a type with `as`, to cast it for the right-hand side:
+/// `Foo::N(x as u64) => Bar::N(x)`. The hint is left out of the pattern, and `x` is cast before `Bar::N(x)` is built.
+/// The reverse casts it back with `as _`, so its target is always the type of the field on the left-hand side.
+/// Nothing is checked about the casts, so they only round-trip for values which fit into both types
+/// (e.g. widening a `u32` into a `u64`, which is cast back losslessly):
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     N(u32, bool),
+///     Pos { x: u32, y: u8 },
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     N(u64, bool),
+///     Pos { x: u64, y: u64 },
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::N(n as u64, flag) => Bar::N(n, flag),
+///     Foo::Pos { x as u64, y: row as u64 } => Bar::Pos { x, y: row },
+/// });
+///
+/// assert_eq!(Bar::from(Foo::N(7, true)), Bar::N(7, true));
+/// assert_eq!(Foo::from(Bar::Pos { x: 1, y: 2 }), Foo::Pos { x: 1, y: 2 });
+/// ```
+///
+/// Hints are only read on plain bindings directly inside the group of the left-hand side,
+/// in branches separated by commas. They can't be combined with [`#[auto_into]`](#auto_into),
+/// which would pass the binding to `Into::into` after the cast back, leaving nothing to infer its target from.
+/// Hinted branches are read one at a time, so they may be mixed with any other branches
+/// (e.g. `Foo::A(Some(x))`). A hint in the same group as a nested pattern (e.g. `Foo::A(n as u64, Some(x))`)
+/// isn't read, and the pattern fails to compile.
+///
+/// ## Wildcards
+/// A wildcard can't be read as an expression, so a catch-all branch must always name its reverse.
+/// The grammar is the same as above, with `_` as the forward pattern:
+/// ```text
+/// _ => <forward expression>; reverse <reverse pattern> => <reverse expression>
+/// ```
+/// Everything not matched by the other branches then maps to the forward expression,
+/// while only the reverse pattern maps back. The wildcard should come last, like in any other `match`.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     Unknown,
+///     Reserved,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Unknown,
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     _ => Bar::Unknown; reverse Bar::Unknown => Foo::Unknown,
+/// });
+///
+/// assert_eq!(Bar::from(Foo::Reserved), Bar::Unknown);
+/// assert_eq!(Foo::from(Bar::Unknown), Foo::Unknown);
+/// ```
+///
+/// ## Forward-only branches
+/// Branches inside a `forward_only { ... }` block only go into the forward `match`, so several values may map to the same one
+/// without an or-pattern. The value they map to still needs a regular branch, which is the one that's read in reverse:
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     Legacy,
+///     Deprecated,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     forward_only {
+///         Foo::Legacy => Bar::X,
+///         Foo::Deprecated => Bar::X,
+///     },
+///     Foo::B => Bar::Y,
+/// });
+///
+/// assert_eq!(Bar::from(Foo::Legacy), Bar::X);
+/// assert_eq!(Foo::from(Bar::X), Foo::A);
+/// ```
+/// These branches are read like in a `match`, so they can use any pattern and expression (but aren't counted by [`#[count]`](#count)).
+/// In [one-directional conversions](#one-directional-conversions), every branch is like this already.
+///
+/// # One-directional conversions
+/// Writing `=>` instead of a comma between the types only generates the forward `From` impl.
+/// The branches are then never read in reverse, so they can be any `match` branches -
+/// this is useful when the reverse direction is not a function (e.g. multiple values map to the same one).
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(Foo => Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B | Foo::C => Bar::Y,
+/// });
+///
+/// assert_eq!(Bar::from(Foo::A), Bar::X);
+/// assert_eq!(Bar::from(Foo::B), Bar::Y);
+/// assert_eq!(Bar::from(Foo::C), Bar::Y);
+/// ```
+///
+/// Likewise, writing `<=` only generates the reverse `From` impl (here `From<Bar> for Foo`).
+/// This mirrors `=>`: each right-hand side is a pattern, and each left-hand side any expression,
+/// so they go into the reverse `match` as `Bar::Y | Bar::Z => Foo::B`. Several branches may share a left-hand side,
+/// the right-hand sides may be or-patterns or `_`, and there are no explicit reverses.
+/// Reverse-only conversions can't be generic.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+///     Z,
+/// }
+///
+/// bijection!(Foo <= Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y | Bar::Z,
+/// });
+///
+/// assert_eq!(Foo::from(Bar::X), Foo::A);
+/// assert_eq!(Foo::from(Bar::Z), Foo::B);
+/// ```
+///
+/// # Borrowed sides
+/// Prefixing one of the types with `&` converts from a reference to it, while the other direction stays owned:
+/// `bijection!(&Foo, Bar, { ... })` generates `From<&Foo> for Bar` and `From<Bar> for Foo`.
+/// This is useful when `Foo` is large (or not `Clone`), and `Bar` is only a small tag.
+///
+/// Like with [`#[borrow]`](#borrow), each pattern is matched through the reference, so bound fields are copied out of it
+/// and must be `Copy`. Fields which aren't can't be bound, and must be matched with `_` (and an explicit reverse) instead.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     Named(String),
+///     Id(u32),
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Bar {
+///     Named,
+///     Id(u32),
+/// }
+///
+/// bijection!(&Foo, Bar, {
+///     Foo::Named(_) => Bar::Named; reverse Bar::Named => Foo::Named(String::new()),
+///     Foo::Id(id) => Bar::Id(id),
+/// });
+///
+/// let foo = Foo::Named("large".to_string());
+/// assert_eq!(Bar::from(&foo), Bar::Named);
+/// assert_eq!(Foo::from(Bar::Id(3)), Foo::Id(3));
+/// ```
+///
+/// # Integers
+/// When the second type is a primitive integer (written as such, e.g. `u8`, not an alias),
+/// not every value has a counterpart when every branch maps to a literal. Instead of a reverse `From`,
+/// a `TryFrom` impl is then generated, which returns the unknown integer as its error.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Sub,
+/// }
+///
+/// bijection!(Op, u8, {
+///     Op::Add => 0,
+///     Op::Sub => 1,
+/// });
+///
+/// assert_eq!(u8::from(Op::Sub), 1);
+/// assert_eq!(Op::try_from(0), Ok(Op::Add));
+/// assert_eq!(Op::try_from(7), Err(7));
+/// ```
+/// A binding covers every value, so `Wrapper(x) => x` generates plain `From` impls both ways.
+/// The same goes for `char`, e.g. for mapping tokens to their characters (`Token::Plus => '+'`),
+/// where the reverse returns the unknown `char`.
+///
+/// Other second types with values which can't all be matched (`f32`, `f64` and `String`) are rejected
+/// when every branch maps to a literal, as the reverse `match` could never be exhaustive.
+/// Use [`try_bijection!`] or an error type (see [Partial types](#partial-types)) for those instead.
+/// A binding still covers every value, as in `Meters(m) => m`.
+/// `bool` has only two values, so it needs no special treatment: `Switch::On => true, Switch::Off => false`
+/// is an exhaustive `match` either way, and generates plain `From` impls.
+/// As a second type, it's rejected if every branch maps to the same literal, as the other value would have no counterpart.
+///
+/// A different error type can be given with `error = ...` after the types.
+/// Unknown integers are then converted into it, so the reverse `match` ends with
+/// `other => Err(From::from(other))`, and the error type must implement `From` for the integer type.
+/// ```rust
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq)]
+/// # enum Op { Add, Sub }
+/// #[derive(Debug, PartialEq)]
+/// enum OpError {
+///     Unknown(u8),
+/// }
+///
+/// impl From<u8> for OpError {
+///     fn from(value: u8) -> Self {
+///         OpError::Unknown(value)
+///     }
+/// }
+///
+/// bijection!(Op, u8, error = OpError, {
+///     Op::Add => 0,
+///     Op::Sub => 1,
+/// });
+///
+/// assert_eq!(Op::try_from(7), Err(OpError::Unknown(7)));
+/// ```
+///
+/// ## Partial types
+/// With `error = ...`, any other second type gets the same fallible reverse, for types whose values can't all be matched.
+/// For example, a bitflags-style struct has a constant for each single flag, but any combination of them is a value as well.
+/// Its constants can be matched like literals (the type must derive `PartialEq` and `Eq` for that),
+/// and any other value is converted into the error type. To get the unknown value back, use the type itself as the error.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Flags(u8);
+///
+/// impl Flags {
+///     const READ: Flags = Flags(0b01);
+///     const WRITE: Flags = Flags(0b10);
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Flag {
+///     Read,
+///     Write,
+/// }
+///
+/// bijection!(Flag, Flags, error = Flags, {
+///     Flag::Read => Flags::READ,
+///     Flag::Write => Flags::WRITE,
+/// });
+///
+/// assert_eq!(Flags::from(Flag::Write), Flags::WRITE);
+/// assert_eq!(Flag::try_from(Flags::READ), Ok(Flag::Read));
+/// assert_eq!(Flag::try_from(Flags(0b11)), Err(Flags(0b11)));
+/// ```
+///
+/// ## String keys
+/// String slices are handled the same way, on either side: with `&'static str` (or `&str`) as one of the types,
+/// the conversion from the strings is a `TryFrom` impl returning the unknown string (or the `error = ...` type),
+/// and the other one converts into `&'static str`. The impls are generic over the lifetime,
+/// so borrowed strings (e.g. from a `String`) can be looked up as well.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Cmd {
+///     Go,
+///     Stop,
+/// }
+///
+/// bijection!(&'static str, Cmd, {
+///     "go" => Cmd::Go,
+///     "stop" => Cmd::Stop,
+/// });
+///
+/// assert_eq!(Cmd::try_from("go"), Ok(Cmd::Go));
+/// assert_eq!(Cmd::try_from(String::from("jump").as_str()), Err("jump"));
+/// assert_eq!(<&str>::from(Cmd::Stop), "stop");
+/// ```
+/// For parsing and printing instead (`FromStr` and `Display`), see [`str_bijection!`].
+///
+/// # Generics
+/// Generic parameters (types and lifetimes) can be declared once before the types,
+/// and are added to every generated impl.
+/// Bounds are optional, and only a single bound per parameter is supported (e.g. `<'a, T: 'a>`, `<T: Clone>`).
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Wrapper<T>(T);
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Other<T>(T);
+///
+/// bijection!(<T> Wrapper<T>, Other<T>, {
+///     Wrapper(x) => Other(x),
+/// });
+///
+/// assert_eq!(Other::from(Wrapper(1)), Other(1));
+/// assert_eq!(Wrapper::from(Other("one")), Wrapper("one"));
+/// ```
+///
+/// A `where` clause may follow the types, which is added to every generated impl as well.
+/// ```text
+/// bijection!(<T> Wrapper<T>, Other<T> where T: Clone + Debug, { ... });
+/// ```
+///
+/// # Modules
+/// The generated items may be wrapped in a module, by starting the invocation with `mod name;`.
+/// Attributes before `mod` (e.g. `#[cfg(...)]`) and a visibility are placed on the module,
+/// while flags still go before the types: `bijection!(#[cfg(feature = "serde")] pub mod conv; #[by_ref] Foo, Bar, { ... })`.
+/// The module imports everything from its parent, so the types can be named as usual,
+/// unless they are declared inside a function body (a nested module can't see those).
+/// Impls apply regardless of the module they are written in, so this is only for grouping the generated code.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+/// }
+///
+/// bijection!(mod conv; Foo, Bar, {
+///     Foo::A => Bar::X,
+/// });
+///
+/// fn main() {
+///     assert_eq!(Bar::from(Foo::A), Bar::X);
+/// }
+/// ```
+///
+/// # Imports
+/// The block may start with `use` declarations, which are in scope for all of its branches,
+/// e.g. to name the variants of both types without their paths.
+/// The generated code is placed in an anonymous `const` block together with them, so they don't leak into the module.
+/// Samples for `test_samples` (see [Roundtrip tests](#roundtrip-tests)) are outside of it, and have to spell out their paths.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Direction {
+///     North,
+///     South,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Heading {
+///     Up,
+///     Down,
+/// }
+///
+/// bijection!(Direction, Heading, {
+///     use Direction::*;
+///     use Heading::*;
+///
+///     North => Up,
+///     South => Down,
+/// });
+///
+/// assert_eq!(Heading::from(Direction::North), Heading::Up);
+/// assert_eq!(Direction::from(Heading::Down), Direction::South);
+/// ```
+///
+/// # Declaration block first
+/// With long type paths, the declaration block may be given first, followed by `for` and the types (including any flags or generics):
+/// ```rust
+/// use biject_into::bijection;
+///
+/// mod very {
+///     pub mod long {
+///         pub mod path {
+///             #[derive(Debug, PartialEq)]
+///             pub enum Foo {
+///                 A,
+///             }
+///         }
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+/// }
+///
+/// use very::long::path::Foo;
+///
+/// bijection!({
+///     Foo::A => Bar::X,
+/// } for #[by_ref] very::long::path::Foo, Bar);
+///
+/// assert_eq!(Bar::from(&Foo::A), Bar::X);
+/// assert_eq!(Foo::from(Bar::X), Foo::A);
+/// ```
+/// There can't be a trailing comma after the types, or anything else after them (like `test_samples`).
+///
+/// # Flags
+/// Flags may be given as attributes before the types, e.g. `bijection!(#[by_ref] Foo, Bar, { ... })`.
+///
+/// ## `#[by_ref]`
+/// Additionally generates `From<&Foo> for Bar` and `From<&Bar> for Foo`.
+/// These match on the dereferenced value (`match *value`), so the patterns stay exactly as written,
+/// and any bindings are copied out of the reference. Every bound field must therefore be `Copy`,
+/// but the types themselves don't need to be.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B { x: u32 },
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y(u32),
+/// }
+///
+/// bijection!(#[by_ref] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B { x } => Bar::Y(x),
+/// });
+///
+/// let foo = Foo::B { x: 5 };
+/// assert_eq!(Bar::from(&foo), Bar::Y(5));
+/// assert_eq!(Bar::from(foo), Bar::Y(5));
+/// assert_eq!(Foo::from(&Bar::X), Foo::A);
+/// ```
+/// Bindings of non-`Copy` fields (such as a `String`) will not compile, as they cannot be moved out of the reference.
+///
+/// ## `#[by_mut]`
+/// Additionally generates `From<&mut Foo> for Bar` and `From<&mut Bar> for Foo`.
+/// These take the value out of the reference with [`core::mem::take`], leaving its default behind,
+/// and convert it like the owned impls. Both types must therefore implement `Default`
+/// (for generic types, add the bound to the `where` clause).
+/// Unlike with `#[by_ref]`, bound fields don't need to be `Copy`.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Default)]
+/// enum Foo {
+///     #[default]
+///     Empty,
+///     Name(String),
+/// }
+///
+/// #[derive(Debug, PartialEq, Default)]
+/// enum Bar {
+///     #[default]
+///     Nothing,
+///     Label(String),
+/// }
+///
+/// bijection!(#[by_mut] Foo, Bar, {
+///     Foo::Empty => Bar::Nothing,
+///     Foo::Name(s) => Bar::Label(s),
+/// });
+///
+/// let mut foo = Foo::Name("kept".to_string());
+/// assert_eq!(Bar::from(&mut foo), Bar::Label("kept".to_string()));
+/// assert_eq!(foo, Foo::Empty);
+/// ```
+/// Fallible conversions aren't supported, as the value would be taken (and lost) even when the conversion fails.
+///
+/// ## `#[inline]`
+/// Marks the generated `from` functions as `#[inline]`, allowing them to be inlined across crates.
+///
+/// ## `#[must_use]`
+/// Accepted for clarity, but adds nothing: `From::from` is already `#[must_use]` in `core`
+/// (and fallible conversions return a `Result`), so discarded conversions are linted either way.
+/// The attribute can't be placed on the generated `from` functions themselves, as it isn't allowed on trait impl methods.
+///
+/// ## `#[doc]`
+/// Doc attributes are placed on each generated `impl` block, so the conversions show up documented in rustdoc.
+/// Several `#[doc]` attributes are kept as separate lines.
+/// ```rust
+/// # use biject_into::bijection;
+/// # pub enum Foo { A }
+/// # pub enum Bar { X }
+/// bijection!(
+///     #[doc = "Maps wire codes to domain states."]
+///     #[doc = "Every code has exactly one state."]
+///     Foo, Bar, {
+///         Foo::A => Bar::X,
+///     }
+/// );
+/// ```
+///
+/// ## `#[borrow]`
+/// Additionally generates `From<&Foo> for Bar`, but unlike `#[by_ref]`, only in the forward direction.
+/// Each pattern is matched through the reference (e.g. `&Foo::B(x) => Bar::Y(x)`),
+/// so bound fields are copied out of it and must be `Copy`.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B(u32),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y(u32),
+/// }
+///
+/// bijection!(#[borrow] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B(x) => Bar::Y(x),
+/// });
+///
+/// let foo = Foo::B(3);
+/// assert_eq!(Bar::from(&foo), Bar::Y(3));
+/// assert_eq!(Foo::from(Bar::Y(3)), foo);
+/// ```
+/// To bind the fields by reference instead, write a one-directional conversion from the reference itself,
+/// e.g. `bijection!(<'a> &'a Foo => BarView<'a>, { Foo::B(x) => BarView::Y(x) })`,
+/// where `x` is a `&'a u32` through default binding modes.
+///
+/// ## `#[warn_lossy]`
+/// Warns about branches which catch everything on the forward side, but map back from a single value
+/// (e.g. `_ => Bar::Other; reverse Bar::Other => Foo::Unknown`), as the conversion can't round-trip there.
+/// Macros can't emit warnings, so this is reported as the use of a deprecated item (`LossyBranch`).
+/// This is only a heuristic: wildcards and single identifiers are considered catch-alls,
+/// so a unit variant written without a path (such as `None`) is reported as well.
+///
+/// ## `#[strict]`
+/// Also rejects textually identical left-hand sides at compile time, in addition to the right-hand sides (see [Caveats](#caveats)).
+/// Every branch is read in both directions, so a repeated pattern on either side would make the conversion lossy:
+/// ```rust,compile_fail
+/// use biject_into::bijection;
+///
+/// struct Foo(u8);
+/// struct Bar(u8);
+///
+/// bijection!(#[strict] Foo, Bar, {
+///     Foo(0) => Bar(0),
+///     Foo(0) => Bar(1), // Error: Foo(0) is already mapped!
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+/// Like the right-hand side check, this is only a textual comparison (with guards included).
+/// Other overlapping patterns are left to the `unreachable_patterns` lint, which `#[strict]` denies on both generated `match`es:
+/// ```rust,compile_fail
+/// use biject_into::bijection;
+///
+/// struct Foo(u8);
+/// struct Bar(u8);
+///
+/// bijection!(#[strict] Foo, Bar, {
+///     Foo(0) => Bar(0),
+///     Foo(1) => Bar(0x0), // Error: Bar(0x0) is unreachable!
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+///
+/// ## `#[allow_unreachable]`
+/// The opposite of [`#[strict]`](#strict): allows the `unreachable_patterns` lint on both generated `match`es,
+/// for branches that intentionally overlap an earlier one.
+/// Since every branch is still read in both directions, an overlapping branch usually needs an explicit reverse:
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+/// #[derive(Debug, PartialEq)]
+/// struct Bar(u8);
+///
+/// bijection!(#[allow_unreachable] Foo, Bar, {
+///     Foo(x) => Bar(x),
+///     Foo(0) => Bar(0); reverse Bar(0) => Foo(0), // Kept for documentation, never taken
+/// });
+///
+/// assert_eq!(Bar::from(Foo(0)), Bar(0));
+/// ```
+///
+/// ## `#[source_non_exhaustive]`
+/// Ends the forward `match` with a wildcard branch which panics (`_ => unreachable!(...)`,
+/// naming both types in its message), for a first type that is a `#[non_exhaustive]` enum from another crate.
+/// Such an enum can't be matched exhaustively outside of its crate, even if every variant has a branch;
+/// only a variant added in a later version of that crate would reach the wildcard.
+/// The reverse `match` is unaffected, as constructing the variants is not restricted.
+/// The flag has no effect on fallible directions, which end with a catch-all already.
+/// ```rust
+/// use biject_into::bijection;
+/// use core::sync::atomic::Ordering;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Order {
+///     Relaxed,
+///     Release,
+///     Acquire,
+///     AcqRel,
+///     SeqCst,
+/// }
+///
+/// bijection!(#[source_non_exhaustive] Ordering, Order, {
+///     Ordering::Relaxed => Order::Relaxed,
+///     Ordering::Release => Order::Release,
+///     Ordering::Acquire => Order::Acquire,
+///     Ordering::AcqRel => Order::AcqRel,
+///     Ordering::SeqCst => Order::SeqCst,
+/// });
+///
+/// assert_eq!(Order::from(Ordering::AcqRel), Order::AcqRel);
+/// assert_eq!(Ordering::from(Order::SeqCst), Ordering::SeqCst);
+/// ```
+///
+/// ## `#[reverse_default]`
+/// Ends the reverse `match` with a wildcard branch, after all the others: `_ => Default::default()`.
+/// Values of the second type without a branch then convert to the first type's default, which must implement `Default`.
+/// With a fallible reverse (e.g. with [integers](#integers)), this makes it infallible, so a `From` impl is generated instead.
+/// Unlike a wildcard with an explicit reverse, this doesn't touch the forward direction.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Default)]
+/// enum Mode {
+///     #[default]
+///     Auto,
+///     Manual,
+/// }
+///
+/// bijection!(#[reverse_default] Mode, u8, {
+///     Mode::Auto => 0,
+///     Mode::Manual => 1,
+/// });
+///
+/// assert_eq!(Mode::from(1), Mode::Manual);
+/// assert_eq!(Mode::from(9), Mode::Auto);
+/// ```
+///
+/// ## `#[auto_into]`
+/// Converts every bound field with `Into::into` when building the other side,
+/// so fields only need to be convertible instead of having the exact same type.
+/// Both directions are converted, so a bidirectional bijection needs `From` impls both ways;
+/// widening integers (`u8` to `u16`) therefore only works in a one-directional conversion.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A(u8),
+///     B { id: u8 },
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X(u16),
+///     Y { id: u32 },
+/// }
+///
+/// bijection!(#[auto_into] Foo => Bar, {
+///     Foo::A(x) => Bar::X(x),
+///     Foo::B { id } => Bar::Y { id },
+/// });
+///
+/// assert_eq!(Bar::from(Foo::A(3)), Bar::X(3u16));
+/// assert_eq!(Bar::from(Foo::B { id: 7 }), Bar::Y { id: 7u32 });
+/// ```
+/// Any identifier ending a pattern element is considered a binding, so unit variants
+/// have to be written with a path (`Option::None` instead of `None`).
+/// Literals are left as they are, so `Foo::Byte(0u8) => Bar::Word(0u16)` doesn't need the flag at all.
+/// In one-directional conversions, block expressions are not converted either.
+///
+/// ## `#[boxed]`
+/// Additionally generates `From<Box<Foo>> for Box<Bar>` and `From<Box<Bar>> for Box<Foo>`,
+/// which unbox the value, convert it with the owned impl, and box the result again.
+/// This requires the `alloc` feature (enabled by default).
+/// ```rust
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+/// }
+///
+/// bijection!(#[boxed] Foo, Bar, {
+///     Foo::A => Bar::X,
+/// });
+///
+/// assert_eq!(Box::<Bar>::from(Box::new(Foo::A)), Box::new(Bar::X));
+/// assert_eq!(Foo::from(Bar::X), Foo::A);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// ## `#[const(...)]`
+/// Additionally generates inherent `const fn` conversions, as `From` impls can't be `const` (yet).
+/// A method name is given for each direction (with an optional visibility), since they can't be derived from the types:
+/// `#[const(pub to_bar, pub to_foo)]` adds `Foo::to_bar(self) -> Bar` and `Bar::to_foo(self) -> Foo`.
+/// Only one name is given for one-directional conversions, and fallible ones are not supported.
+///
+/// The branches may only consist of unit variants (or literals); anything binding data is rejected at compile time.
+/// Like any inherent impl, the types must be defined in the same crate.
+/// The methods take `self` by value, so such types should usually be `Copy` as well.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(#[const(pub to_bar, pub to_foo)] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// const BAR: Bar = Foo::B.to_bar();
+/// assert_eq!(BAR, Bar::Y);
+/// assert_eq!(Bar::X.to_foo(), Foo::A);
+/// ```
+///
+/// ## `#[ext]`
+/// Additionally generates an extension trait for each conversion, so `foo.to_bar()` can be written
+/// instead of `Bar::from(foo)` (or `Into::<Bar>::into(foo)`). Requires the `proc` feature.
+///
+/// Unlike `#[const(...)]`, the names are derived from the types, using the last segment of each path
+/// (without generics): the conversion from `Foo` into `Bar` adds `trait FooExt { fn to_bar(self) -> Bar; }`,
+/// implemented for `Foo` only. The trait name capitalizes the first letter and appends `Ext`,
+/// the method name converts the other type to snake_case, splitting words at capitals
+/// (so `HttpStatus` and `HTTPStatus` both give `to_http_status`, and `u8` gives `to_u8`).
+/// Fallible conversions get `try_to_bar(self) -> Result<Bar, E>` instead.
+///
+/// The traits are private, unless given a visibility: `#[ext(pub)]`. As the traits are local,
+/// they can be implemented for foreign types too, e.g. `bool` gets `BoolExt`.
+/// However, a type in two `#[ext]` bijections within the same module would get two traits with the same name,
+/// so these have to be declared in separate modules. Generic bijections are not supported.
+/// ```rust
+/// # #[cfg(feature = "proc")]
+/// # fn main() {
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum HttpStatus {
+///     Ok,
+///     NotFound,
+/// }
+///
+/// bijection!(#[ext] Foo, HttpStatus, {
+///     Foo::A => HttpStatus::Ok,
+///     Foo::B => HttpStatus::NotFound,
+/// });
+///
+/// assert_eq!(Foo::B.to_http_status(), HttpStatus::NotFound);
+/// assert_eq!(HttpStatus::Ok.to_foo(), Foo::A);
+/// # }
+/// # #[cfg(not(feature = "proc"))]
+/// # fn main() {}
+/// ```
+///
+/// ## `#[eq]`
+/// Additionally generates `PartialEq<Bar> for Foo` and `PartialEq<Foo> for Bar`,
+/// which convert the left-hand side and compare it with the right-hand side.
+/// The converted type must be `Clone`, unless the impls are also `#[by_ref]`.
+/// With a fallible direction (e.g. [`try_bijection!`]), values that fail to convert are never equal.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(#[eq] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// assert!(Foo::A == Bar::X);
+/// assert!(Bar::Y != Foo::A);
+/// ```
+/// The comparison is only meaningful if the branches form a true bijection.
+/// Otherwise, `foo == bar` and `bar == foo` may disagree, as each one converts a different side.
+///
+/// ## `#[table]`
+/// For [integer](#integers) bijections, replaces the reverse `match` with a binary search in a generated table,
+/// which can keep the code smaller for enums with hundreds of variants. The forward direction is still a `match`.
+///
+/// The table is added to the first type as `pub const LOOKUP: &'static [(u32, Foo)]` (with the actual types),
+/// holding one `(value, variant)` pair for each branch, sorted by the value.
+/// The branches may be written in any order, as the table is sorted at compile time.
+/// Every branch must map a unit variant to a literal (without attributes),
+/// and the first type must be `Clone` (usually `Copy`), since the found variant is cloned out of the table.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Status {
+///     Ok,
+///     NotFound,
+///     Teapot,
+/// }
+///
+/// bijection!(#[table] Status, u16, {
+///     Status::Ok => 200,
+///     Status::Teapot => 418,
+///     Status::NotFound => 404,
+/// });
+///
+/// assert_eq!(Status::LOOKUP, &[(200, Status::Ok), (404, Status::NotFound), (418, Status::Teapot)]);
+/// assert_eq!(Status::try_from(404), Ok(Status::NotFound));
+/// assert_eq!(Status::try_from(500), Err(500));
+/// assert_eq!(u16::from(Status::Teapot), 418);
+/// ```
+///
+/// ## `#[fallback]`
+/// For fallible conversions (see [`try_bijection!`]), values not covered by any branch are passed to a function
+/// instead of becoming an error right away. This way, a base mapping can handle the common cases,
+/// and leave the rest to another conversion: `#[fallback(foo_to_bar, bar_to_foo)]`, one function for each direction.
+/// Each function takes the unmatched value, and returns a `Result` whose error converts into the one of the bijection.
+/// A direction can be skipped with `_`, e.g. `#[fallback(_, u8_to_op)]` for the fallible reverse of an [integer](#integers) bijection.
+/// ```rust
+/// use biject_into::{try_bijection, BijectionError};
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Foo {
+///     A,
+///     Plugin(u8),
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Bar {
+///     X,
+///     Extension(u8),
+/// }
+///
+/// fn plugin_to_bar(foo: Foo) -> Result<Bar, BijectionError<Foo>> {
+///     match foo {
+///         Foo::Plugin(n) if n < 10 => Ok(Bar::Extension(n)),
+///         other => Err(BijectionError { value: other }),
+///     }
+/// }
+///
+/// fn bar_to_plugin(bar: Bar) -> Result<Foo, BijectionError<Bar>> {
+///     match bar {
+///         Bar::Extension(n) => Ok(Foo::Plugin(n)),
+///         other => Err(BijectionError { value: other }),
+///     }
+/// }
+///
+/// try_bijection!(#[fallback(plugin_to_bar, bar_to_plugin)] Foo, Bar, {
+///     Foo::A => Bar::X,
+/// });
+///
+/// assert_eq!(Bar::try_from(Foo::Plugin(3)), Ok(Bar::Extension(3)));
+/// assert_eq!(Foo::try_from(Bar::Extension(3)), Ok(Foo::Plugin(3)));
+/// assert!(Bar::try_from(Foo::Plugin(10)).is_err());
+/// ```
+///
+/// ## `#[repr_cast]`
+/// For an enum with a primitive representation (e.g. `#[repr(u8)]`), generates the conversion into the integer
+/// as a cast (`op as u8`) instead of a `match`. The reverse is still a fallible `TryFrom` with a `match`.
+/// The branches must map each unit variant to its actual discriminant, which is asserted at compile time:
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Op {
+///     Nop = 0,
+///     Add = 0x10,
+///     Sub = 0x11,
+/// }
+///
+/// bijection!(#[repr_cast] Op, u8, {
+///     Op::Nop => 0,
+///     Op::Add => 0x10,
+///     Op::Sub => 0x11, // `Op::Sub => 0x12` wouldn't compile
+/// });
+///
+/// assert_eq!(u8::from(Op::Sub), 0x11);
+/// assert_eq!(Op::try_from(0x10), Ok(Op::Add));
+/// ```
+/// Branches with a guard or in a `forward_only` block are not checked, and wouldn't be used by the cast anyway.
+///
+/// ## `#[count]`
+/// Adds the number of branches to the first type, as `pub const BIJECTION_ARMS: usize`
+/// (so the type must be defined in the same crate, and can't be a string slice).
+/// This can be compared with the number of variants in a test, to notice a forgotten branch.
+/// Branches removed by `#[cfg(...)]` are not counted, and a branch with an explicit reverse counts once.
+/// ```rust
+/// use biject_into::bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(#[count] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// assert_eq!(Foo::BIJECTION_ARMS, 2);
+/// ```
+///
+/// The branches are counted as written, even if another flag (like [`#[reverse_default]`](#reverse_default)) adds a wildcard.
+///
+/// ## `#[same_arity]`
+/// Asserts at compile time that there are as many branches as the given number of variants, e.g. `#[same_arity(3)]`.
+/// The branches are counted like with [`#[count]`](#count). The number has to be given, as the variant count
+/// of an enum isn't available on stable Rust (`core::mem::variant_count` is unstable).
+///
+/// Between two unit-only enums, the exhaustive `match`es and the duplicate checks already reject a mismatch,
+/// so this is mostly useful for fallible conversions (see [`try_bijection!`]), where a forgotten branch would compile:
+/// ```rust,compile_fail
+/// use biject_into::try_bijection;
+///
+/// enum Foo {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// enum Bar {
+///     X,
+///     Y,
+///     Z,
+/// }
+///
+/// try_bijection!(#[same_arity(3)] Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+///     // Error: Foo::C => Bar::Z is missing!
+/// });
+/// ```
+///
+/// # Roundtrip tests
+/// Sample values may be listed after the declaration block, which generates a `#[test]`
+/// converting each of them there and back again, and asserting that they come back unchanged.
+/// This requires the first type to implement `PartialEq`, `Debug` and `Clone`.
+/// ```rust
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Foo(i32);
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Bar(i32);
+/// bijection!(Foo, Bar, {
+///     Foo(0) => Bar(1),
+///     Foo(1) => Bar(0),
+///     Foo(x) => Bar(x),
+/// } test_samples(foo_bar_roundtrip) = [Foo(0), Foo(1), Foo(2)]);
+/// ```
+/// The test is only compiled with `cfg(test)`, and is named by `test_samples(...)`,
+/// so several bijections in the same module can each have their own.
+///
+/// # Caveats
+///
+/// ## Unreachable patterns
+/// By itself, the macro does not enforce bijection.
+/// Right-hand sides which are textually identical are rejected at compile time:
+/// ```rust,compile_fail
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Foo(i32);
+///
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Bar(i32);
+///
+/// bijection!(Foo, Bar, {
+///     Foo(0) => Bar(0),
+///     Foo(1) => Bar(0), // Error: Bar(0) is already mapped!
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+/// However, this is only a textual comparison, which cannot catch every duplicate
+/// (nor any duplicates on the left-hand side):
+/// ```rust
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Foo(i32);
+///
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Bar(i32);
+///
+///
+/// bijection!(Foo, Bar, {
+///     Foo(0) => Bar(0),
+///     Foo(1) => Bar(0x0), // Bar(0x0) is unreachable!
+///     Foo(1) => Bar(1), // Foo(1) is unreachable!
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+/// Luckily, this will still cause the macro to emit `unreachable_patterns` warnings,
+/// and even highlight the problematic patterns for you.
+///
+/// You may wrap the macro in a block (or a module) and annotate it with `#[deny(unreachable_patterns)]`.
+/// To reject textually identical left-hand sides as well, use the [`#[strict]`](#strict) flag,
+/// which also denies the lint on the generated `match`es.
+///
+/// ## Included branches
+/// The declaration block can't be read from a file with `bijection!(Foo, Bar, include!("foo_bar.in"))`,
+/// as `include!` is only expanded after the bijection macro has already matched its tokens.
+/// Instead, the file should contain the whole invocation, and be included in its place:
+/// ```rust,ignore
+/// // foo_bar.in
+/// bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// // lib.rs
+/// include!("foo_bar.in");
+/// ```
+///
+/// ## `no_std`
+/// The crate is `#![no_std]`, and the generated code only refers to `::core` (and the crate itself) by full paths,
+/// so it also works in `#![no_std]` crates, and in modules without a prelude (`#[no_implicit_prelude]`).
+/// Only [`#[boxed]`](#boxed) needs `Box`, which comes from `alloc` behind the `alloc` feature (enabled by default);
+/// `default-features = false` drops the dependency on `alloc`, and `#[boxed]` is rejected with an error then.
+///
+/// ## Empty blocks
+/// An empty block (`bijection!(Foo, Bar, {})`) is allowed, as it's complete for empty enums.
+/// For any other type, the non-exhaustive `match` error names `EmptyBijectionBlock<Foo>` instead of `Foo`,
+/// and notes its definition, which reads "The bijection block is empty - did you forget the branches?".
+///
+/// ## Identical types
+/// A bijection between a type and itself is rejected (`Cannot create a bijection between a type and itself`),
+/// as its impls would conflict with `From<T> for T`. The types are only compared textually, so aliases are not caught.
+///
+/// ## `TryFrom` with a custom error
+/// Every `From` impl already provides `TryFrom` (with `Infallible` as its error) through a blanket impl in `core`,
+/// so a second `TryFrom` impl with another error type would conflict with it, and there is no `#[also_tryfrom(...)]` flag.
+/// For conversions that should share a `TryFrom` error type with others, use [`try_bijection!`] with that error type instead,
+/// which generates `TryFrom` impls (and no `From` impls) even if the branches cover every value.
+///
+/// ## Collections and options
+/// There are no `#[vec]` or `#[option]` flags (unlike [`#[boxed]`](#boxed)): `Vec` and `Option` are not fundamental types,
+/// so `From<Vec<Foo>> for Vec<Bar>` and `From<Option<Foo>> for Option<Bar>` are foreign impls, disallowed by the orphan rule.
+/// The same goes for the values of a map (there is no `#[map_values]` either), `HashMap<K, Foo>` to `HashMap<K, Bar>`.
+/// Shared pointers aren't fundamental either, so there is no `#[arc]` (or `#[rc]`) for `From<Arc<Foo>> for Arc<Bar>`;
+/// convert a clone of the contents instead, `Arc::new(Bar::from((*foo).clone()))`.
+/// Containers of your own can be converted element by element with [`bijection_container!`],
+/// which can also implement a conversion trait of your own for the standard ones.
+/// Their contents can still be converted with `Into::into`:
+/// ```rust
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq)]
+/// # enum Foo {
+/// #     A,
+/// # }
+/// # #[derive(Debug, PartialEq)]
+/// # enum Bar {
+/// #     X,
+/// # }
+/// # bijection!(Foo, Bar, {
+/// #     Foo::A => Bar::X,
+/// # });
+/// let bars: Vec<Bar> = vec![Foo::A].into_iter().map(Into::into).collect();
+/// assert_eq!(bars, vec![Bar::X]);
+/// assert_eq!(Some(Foo::A).map(Bar::from), Some(Bar::X));
+/// assert_eq!(None::<Foo>.map(Bar::from), None);
+///
+/// use std::collections::HashMap;
+///
+/// let foos = HashMap::from([(1, Foo::A)]);
+/// let bars: HashMap<i32, Bar> = foos.into_iter().map(|(k, v)| (k, v.into())).collect();
+/// assert_eq!(bars, HashMap::from([(1, Bar::X)]));
+/// ```
+///
+/// ## Bijection branches
+/// The bijection branches are structured to look like `match` branches, but unlike the latter,
+/// or-patterns (or any ambiguous patterns) are disallowed.
+/// ```rust,compile_fail
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Foo(i32);
+///
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Bar(i32);
+///
+/// bijection!(Foo, Bar, {
+///     // No or-pattern! That would be like writing Bar(0) => Foo(0) | Foo(1)
+///     Foo(0) | Foo(1) => Bar(0),
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+/// Inner or-patterns are rejected as well - on the reverse side, `Foo(2 | 3)` would otherwise
+/// be read as an expression, silently producing a bitwise or (`Foo(3)`).
+/// ```rust,compile_fail
+/// # use biject_into::bijection;
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Foo(i32);
+///
+/// # #[derive(Debug, PartialEq, Clone)]
+/// # struct Bar(i32);
+///
+/// bijection!(Foo, Bar, {
+///     Foo(2 | 3) => Bar(1),
+///     Foo(x) => Bar(x),
+/// });
+/// ```
+#[macro_export]
+macro_rules! bijection {
+    // Final construction of the From impls
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@reject_same_types $first_ty, $second_ty);
+        $crate::bijection!(@branch_docs $generics [$($flag)*] { $($first_done)* } { $($second_done)* }
+            { from $first_ty => $second_ty { $($first_done)* } }
+            { from $second_ty => $first_ty { $($second_done)* } }
+        );
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    // Final construction of the forward From impl only
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, forward)
+        { $($first_done:tt)* }
+        {}
+        ()
+    ) => {
+        $crate::bijection!(@reject_same_types $first_ty, $second_ty);
+        $crate::bijection!(@impl $generics [#[@sort_flags] $($flag)*] {} {} owned
+            { from $first_ty => $second_ty { $($first_done)* } }
+        );
+    };
+
+    // Final construction of the TryFrom impls (see `try_bijection!`)
+    (@
+    // Each direction has its own error type, as the default one holds the unmapped value
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, try $first_error_ty:ty, $second_error_ty:ty)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@reject_same_types $first_ty, $second_ty);
+        $crate::bijection!(@branch_docs $generics [$($flag)*] { $($first_done)* } { $($second_done)* }
+            { (try_from $first_error_ty) $first_ty => $second_ty { $($first_done)* } }
+            { (try_from $second_error_ty) $second_ty => $first_ty { $($second_done)* } }
+        );
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    // Final construction of the string conversions (see `str_bijection!`)
+    // Parsing is fallible, so the reverse `match` ends with a catch-all like the TryFrom impls
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, str $error_ty:ty)
+        { $($first_done:tt)* }
+        { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* }
+        ()
+    ) => {
+        #[automatically_derived]
+        impl ::core::fmt::Display for $first_ty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.pad(match self {
+                    $($first_done)*
+                })
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::str::FromStr for $first_ty {
+            type Err = $error_ty;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match s {
+                    $($(#[$($attr)*])* $pat $(if $guard)? => ::core::result::Result::Ok($expr),)*
+                    _ => ::core::result::Result::Err(::core::convert::From::from(s)),
+                }
+            }
+        }
+
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($(#[$($attr)*])* $pat $(if $guard)? => $expr,)* });
+    };
+
+    // Final construction of the integer conversions
+    // Not every integer has a variant, so only the reverse direction is fallible
+    // (returning the integer back, or converting it into the given error type)
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, integer $error_ty:ty)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@branch_docs $generics [$($flag)*] { $($first_done)* } { $($second_done)* }
+            { from $first_ty => $second_ty { $($first_done)* } }
+            { (try_from $error_ty) $second_ty => $first_ty { $($second_done)* } }
+        );
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    // Final construction of the string key conversions
+    // The mirror image of the integer conversions, with the strings on the fallible forward side
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, str_key $error_ty:ty)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@branch_docs $generics [$($flag)*] { $($first_done)* } { $($second_done)* }
+            { (try_from $error_ty) $first_ty => $second_ty { $($first_done)* } }
+            { from $second_ty => $first_ty { $($second_done)* } }
+        );
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    // Final construction of inherent conversion methods (see `bijection_methods!`)
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, methods $first_methods:tt $second_methods:tt)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@reject_same_types $first_ty, $second_ty);
+        $crate::bijection!(@methods $first_ty, $second_ty $first_methods { $($first_done)* } { $($second_done)* });
+        $crate::bijection!(@methods $second_ty, $first_ty $second_methods { $($second_done)* } { $($first_done)* });
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    (@methods $self_ty:ty, $other_ty:ty [] $into_arms:tt $from_arms:tt) => {};
+
+    (@methods $self_ty:ty, $other_ty:ty
+        [$into_vis:vis $into_name:ident, $from_vis:vis $from_name:ident]
+        { $($into_arms:tt)* }
+        { $($from_arms:tt)* }
+    ) => {
+        impl $self_ty {
+            $into_vis fn $into_name(self) -> $other_ty {
+                match self {
+                    $($into_arms)*
+                }
+            }
+
+            $from_vis fn $from_name(value: $other_ty) -> Self {
+                match value {
+                    $($from_arms)*
+                }
+            }
+        }
+    };
+
+    // Final construction of the trait impls (see `bijection_trait!`)
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty, trait $trait_path:tt)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ()
+    ) => {
+        $crate::bijection!(@reject_same_types $first_ty, $second_ty);
+        $crate::bijection!(@trait_impl $trait_path $first_ty => $second_ty { $($first_done)* });
+        $crate::bijection!(@trait_impl $trait_path $second_ty => $first_ty { $($second_done)* });
+        $crate::bijection!(@reject_duplicates { $($first_done)* } { $($second_done)* });
+    };
+
+    (@trait_impl [$($trait_seg:ident)::+] $self_ty:ty => $other_ty:ty { $($arms:tt)* }) => {
+        #[automatically_derived]
+        impl $($trait_seg)::+ <$other_ty> for $self_ty {
+            fn convert(self) -> $other_ty {
+                match self {
+                    $($arms)*
+                }
+            }
+        }
+    };
+
+    // ===== Branch docs =====
+    // Ex: /// add op
+    //     Foo::Add => Bar::Add,
+    // Each doc line of a branch becomes a row of the impl docs ("- add op: `Foo::Add` ↔ `Bar::Add`").
+    // The finished branches are passed twice: read as `match` branches for the rows, and as-is for the impls,
+    // as captured patterns couldn't be matched as tokens anymore (e.g. by `#[table]`).
+    // Both groups have one `match` branch for each bijection branch, so they can be zipped.
+    // Doc attributes can't be told apart from the others in a single matcher, so every attribute
+    // turns into a `#[doc]`, where `@doc_row` leaves the others empty (which clippy would point out).
+    // The rows are added as the last flag, so they come after any `#[doc]` flags.
+    // The docs are still copied onto the `match` arms like other attributes, where rustdoc ignores them.
+    (@branch_docs $generics:tt [$($flag:tt)*]
+        { $( $(#[$attr_name:ident $($attr_arg:tt)*])* $first_pat:pat $(if $first_guard:expr)? => $first_expr:expr, )* }
+        { $( $(#[$($second_attr:tt)*])* $second_pat:pat $(if $second_guard:expr)? => $second_expr:expr, )* }
+        $($group:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [#[@sort_flags] $($flag)* #[@branch_docs #[allow(clippy::empty_docs)] $($(
+            #[doc = $crate::bijection!(@doc_row $attr_name ($($attr_arg)*) $first_pat, $second_pat)]
+        )*)*]] { #[allow(unused_doc_comments)] } {} owned $($group)*);
+    };
+
+    (@doc_row doc (= $doc:literal) $first_pat:tt, $second_pat:tt) => {
+        concat!("- ", $doc, ": `", stringify!($first_pat), "` ↔ `", stringify!($second_pat), "`")
+    };
+    (@doc_row $attr_name:ident $attr_args:tt $first_pat:tt, $second_pat:tt) => {
+        ""
+    };
+
+    // ===== Flags =====
+    // Flags are munched one by one before writing the impls.
+    // The state consists of the attributes for the generated fns and impls, and whether to match by reference.
+    // The impls themselves are passed along as `{ kind Src => Dst { branches } }` groups.
+
+    // Added before the other flags by the final constructions
+    // Flags reading the branches are moved to the front, so they see the branches as written,
+    // before other flags add a wildcard (e.g. `#[reverse_default]`) or duplicate the groups (e.g. `#[eq]`)
+    (@impl $generics:tt [#[@sort_flags] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@sort_flags [] [] [$($flag)*] $generics $($rest)*);
+    };
+
+    (@sort_flags [$($counting:tt)*] $other:tt [#[count] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@sort_flags [$($counting)* #[count]] $other [$($flag)*] $($rest)*);
+    };
+    (@sort_flags [$($counting:tt)*] $other:tt [#[same_arity $($arg:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@sort_flags [$($counting)* #[same_arity $($arg)*]] $other [$($flag)*] $($rest)*);
+    };
+    (@sort_flags [$($counting:tt)*] $other:tt [#[repr_cast] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@sort_flags [$($counting)* #[repr_cast]] $other [$($flag)*] $($rest)*);
+    };
+    (@sort_flags $counting:tt [$($other:tt)*] [#[$($attr:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@sort_flags $counting [$($other)* #[$($attr)*]] [$($flag)*] $($rest)*);
+    };
+    (@sort_flags [$($counting:tt)*] [$($other:tt)*] [] $generics:tt $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($counting)* $($other)*] $($rest)*);
+    };
+
+    // Ex: bijection!(#[by_ref] Foo, Bar, { ... })
+    // Adds `From<&Foo>` impls; their `match` reads through the reference, so bindings are copied out of it
+    (@impl $generics:tt [#[by_ref] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs by_ref $($rest)*);
+    };
+
+    // Ex: bijection!(#[inline] Foo, Bar, { ... })
+    (@impl $generics:tt [#[inline] $($flag:tt)*] { $($fn_attr:tt)* } $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] { $($fn_attr)* #[inline] } $($rest)*);
+    };
+
+    // Ex: bijection!(#[allow_unreachable] Foo, Bar, { ... })
+    // Goes on the generated functions, which covers the `match` in each of them
+    (@impl $generics:tt [#[allow_unreachable] $($flag:tt)*] { $($fn_attr:tt)* } $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] { $($fn_attr)* #[allow(unreachable_patterns)] } $($rest)*);
+    };
+
+    // Ex: bijection!(#[must_use] Foo, Bar, { ... })
+    // `From::from` is already `#[must_use]`, and the attribute isn't allowed on trait impl methods
+    (@impl $generics:tt [#[must_use] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $($rest)*);
+    };
+
+    // Ex: bijection!(#[doc = "Maps wire codes to domain states"] Foo, Bar, { ... })
+    // Doc attributes are kept separate, so multiple lines stay multiple lines
+    (@impl $generics:tt [#[doc $($doc:tt)*] $($flag:tt)*] $fn_attrs:tt { $($impl_attr:tt)* } $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs { $($impl_attr)* #[doc $($doc)*] } $($rest)*);
+    };
+
+    // Added by `@branch_docs` with the rows of documented branches (if any)
+    (@impl $generics:tt [#[@branch_docs $($row:tt)*] $($flag:tt)*] $fn_attrs:tt { $($impl_attr:tt)* } $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs { $($impl_attr)* $($row)* } $($rest)*);
+    };
+
+    // Ex: bijection!(#[strict] Foo, Bar, { ... })
+    // The right-hand sides are always checked, this adds the same check for the left-hand sides (the first group).
+    // Overlapping patterns are denied on the generated functions, which covers the `match` in each of them.
+    (@impl $generics:tt [#[strict] $($flag:tt)*] { $($fn_attr:tt)* } $impl_attrs:tt $by_ref:ident
+        { $kind:tt $src_ty:ty => $dst_ty:ty { $($arms:tt)* } }
+        $($group:tt)*
+    ) => {
+        $crate::bijection!(@reject_duplicate_patterns [] $($arms)*);
+        $crate::bijection!(@impl $generics [$($flag)*] { $($fn_attr)* #[deny(unreachable_patterns)] } $impl_attrs $by_ref
+            { $kind $src_ty => $dst_ty { $($arms)* } }
+            $($group)*
+        );
+    };
+
+    // Ex: bijection!(#[source_non_exhaustive] Foo, Bar, { ... })
+    // A `#[non_exhaustive]` enum from another crate needs a wildcard, even if every variant has a branch.
+    // Only the forward `match` (the first group) is affected, fallible ones end with a catch-all already.
+    (@impl $generics:tt [#[source_non_exhaustive] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { (try_from $error_ty:ty) $($impl:tt)* }
+        $($group:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { (try_from $error_ty) $($impl)* }
+            $($group)*
+        );
+    };
+
+    (@impl $generics:tt [#[source_non_exhaustive] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { $kind:tt $src_ty:ty => $dst_ty:ty { $($arms:tt)* } }
+        $($group:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { $kind $src_ty => $dst_ty {
+                $($arms)*
+                #[allow(unreachable_patterns)]
+                _ => ::core::unreachable!(concat!(
+                    "No bijection branch for this ", stringify!($src_ty), " value (converting into ", stringify!($dst_ty), ")"
+                )),
+            } }
+            $($group)*
+        );
+    };
+
+    // Ex: bijection!(#[reverse_default] Foo, Bar, { ... })
+    // The reverse `match` (the second group) ends with a wildcard producing `Default::default()`.
+    // A fallible reverse (e.g. with integers) becomes infallible this way, so it's turned into a `From` impl.
+    (@impl $generics:tt [#[reverse_default] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $first:tt
+        { $kind:tt $src_ty:ty => $dst_ty:ty { $($arms:tt)* } }
+        $($group:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $first
+            { from $src_ty => $dst_ty {
+                $($arms)*
+                #[allow(unreachable_patterns)]
+                _ => <$dst_ty as ::core::default::Default>::default(),
+            } }
+            $($group)*
+        );
+    };
+
+    (@impl $generics:tt [#[reverse_default] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[reverse_default] needs a reverse conversion, it can't be used with a one-directional one");
+    };
+
+    // Ex: bijection!(#[auto_into] Foo, Bar, { ... })
+    // The bindings were already converted while reading the sides as expressions
+    (@impl $generics:tt [#[auto_into] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $($rest)*);
+    };
+
+    // Ex: bijection!(#[warn_lossy] Foo, Bar, { ... })
+    // This is checked while munching the branches (see "Lossy branches"), so there's nothing to add here
+    (@impl $generics:tt [#[warn_lossy] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $($rest)*);
+    };
+
+    // Ex: bijection!(#[borrow] Foo, Bar, { ... })
+    // Adds `From<&Foo> for Bar` for the forward direction only (the first group)
+    (@impl $generics:tt [#[borrow] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { $first_kind:tt $($first:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { $first_kind $($first)* }
+            $($rest)*
+            { (borrow $first_kind) $($first)* }
+        );
+    };
+
+    // Ex: bijection!(&Foo, Bar, { ... })
+    // Added by the entry after any other flags, so the borrowing impl replaces the owned one
+    (@impl $generics:tt [#[@borrow_first] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { $first_kind:tt $($first:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { (borrow $first_kind) $($first)* }
+            $($rest)*
+        );
+    };
+
+    // Ex: bijection!(Foo, &Bar, { ... })
+    (@impl $generics:tt [#[@borrow_second] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $first:tt
+        { $second_kind:tt $($second:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $first
+            { (borrow $second_kind) $($second)* }
+            $($rest)*
+        );
+    };
+
+    // Ex: bijection!(#[boxed] Foo, Bar, { ... })
+    // Adds `From<Box<Foo>> for Box<Bar>` impls next to the owned ones, duplicated as `(boxed kind)` groups.
+    // Without the `alloc` feature, only the owned impls are kept, next to an error (see `__boxed_without_alloc`).
+    (@impl $generics:tt [#[boxed] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $({ $kind:tt $($impl:tt)* })*
+    ) => {
+        $crate::__private::boxed! {
+            {
+                $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+                    $({ $kind $($impl)* })*
+                    $({ (boxed $kind) $($impl)* })*
+                );
+            }
+            {
+                $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+                    $({ $kind $($impl)* })*
+                );
+            }
+        }
+    };
+
+    // Ex: bijection!(#[by_mut] Foo, Bar, { ... })
+    // Adds `From<&mut Foo> for Bar` impls next to the owned ones, duplicated as `(by_mut kind)` groups
+    (@impl $generics:tt [#[by_mut] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $({ $kind:tt $($impl:tt)* })*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $({ $kind $($impl)* })*
+            $({ (by_mut $kind) $($impl)* })*
+        );
+    };
+
+    // Ex: bijection!(#[const(pub to_bar, pub to_foo)] Foo, Bar, { ... })
+    // Adds inherent `const fn` conversions, as trait impls can't be `const` yet.
+    // Names are given for the original impls (the first groups), one for each direction.
+    (@impl $generics:tt [#[const($first_vis:vis $first_name:ident, $second_vis:vis $second_name:ident $(,)?)] $($flag:tt)*]
+        $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { $first_kind:tt $($first:tt)* }
+        { $second_kind:tt $($second:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { $first_kind $($first)* }
+            { $second_kind $($second)* }
+            $($rest)*
+            { (const [$first_vis $first_name] $first_kind) $($first)* }
+            { (const [$second_vis $second_name] $second_kind) $($second)* }
+        );
+    };
+
+    // Ex: bijection!(#[const(pub to_bar)] Foo => Bar, { ... })
+    (@impl $generics:tt [#[const($first_vis:vis $first_name:ident $(,)?)] $($flag:tt)*]
+        $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { $first_kind:tt $($first:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { $first_kind $($first)* }
+            $($rest)*
+            { (const [$first_vis $first_name] $first_kind) $($first)* }
+        );
+    };
+
+    (@impl $generics:tt [#[const $($unknown:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Const conversions need a method name for each direction, e.g. #[const(to_bar, to_foo)]");
+    };
+
+    // Ex: bijection!(#[ext(pub)] Foo, Bar, { ... })
+    // Adds an extension trait for each conversion, named by the proc macro (as identifiers can't be pasted here).
+    // Only the original impls get one, the groups added by other flags (e.g. `(eq from)`) are skipped.
+    (@impl $generics:tt [#[ext] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [#[@ext []] $($flag)*] $($rest)*);
+    };
+    (@impl $generics:tt [#[ext($vis:vis)] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [#[@ext [$vis]] $($flag)*] $($rest)*);
+    };
+    (@impl ([] []) [#[@ext $vis:tt] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $({ $kind:tt $($group:tt)* })*
+    ) => {
+        $( $crate::bijection!(@ext $vis $kind $($group)*); )*
+        $crate::bijection!(@impl ([] []) [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $({ $kind $($group)* })*
+        );
+    };
+    (@impl $generics:tt [#[@ext $vis:tt] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[ext] is not supported for generic bijections, as the extension traits would need the generics too");
+    };
+
+    (@ext $vis:tt from $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::__private::ext!($vis from $src_ty => $dst_ty);
+    };
+    (@ext $vis:tt cast $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::__private::ext!($vis from $src_ty => $dst_ty);
+    };
+    (@ext $vis:tt (try_from $error_ty:ty) $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::__private::ext!($vis try ($error_ty) $src_ty => $dst_ty);
+    };
+    (@ext $vis:tt (table $error_ty:ty) $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::__private::ext!($vis try ($error_ty) $src_ty => $dst_ty);
+    };
+    (@ext $vis:tt $kind:tt $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {};
+
+    // Ex: bijection!(#[eq] Foo, Bar, { ... })
+    // Adds a `PartialEq` impl for each conversion, so the impls are duplicated as `(eq kind)` groups
+    (@impl $generics:tt [#[eq] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $({ $kind:tt $($impl:tt)* })*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $({ $kind $($impl)* })*
+            $({ (eq $kind) $($impl)* })*
+        );
+    };
+
+    // Ex: bijection!(#[count] Foo, Bar, { ... })
+    // Each bijection branch has exactly one reverse `match` branch (the second group), while the forward one
+    // may have gained a wildcard (see `#[source_non_exhaustive]`). One-directional conversions only have the first group.
+    // The branches are counted by their stringified patterns, keeping their attributes so `#[cfg(...)]` applies.
+    (@impl $generics:tt [#[count] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident $first:tt $second:tt $($rest:tt)*) => {
+        $crate::bijection!(@count $generics $second dst);
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref $first $second $($rest)*);
+    };
+    (@impl $generics:tt [#[count] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident $first:tt) => {
+        $crate::bijection!(@count $generics $first src);
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref $first);
+    };
+
+    (@count ([$($generic:tt)*] [$($pred:tt)*])
+        { $kind:tt $src_ty:ty => $dst_ty:ty { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* } }
+        $side:ident
+    ) => {
+        impl<$($generic)*> $crate::bijection!(@count_side $side $src_ty, $dst_ty) where $($pred)* {
+            /// The number of branches in the bijection
+            #[allow(unused_doc_comments)]
+            pub const BIJECTION_ARMS: usize = [$($(#[$($attr)*])* stringify!($pat)),*].len();
+        }
+    };
+    (@count_side src $src_ty:ty, $dst_ty:ty) => { $src_ty };
+    (@count_side dst $src_ty:ty, $dst_ty:ty) => { $dst_ty };
+
+    // Ex: bijection!(#[same_arity(3)] Foo, Bar, { ... })
+    // Counted like `#[count]`, but compared in a const assertion instead
+    (@impl $generics:tt [#[same_arity($count:expr)] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident $first:tt $second:tt $($rest:tt)*) => {
+        $crate::bijection!(@same_arity ($count) $second);
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref $first $second $($rest)*);
+    };
+    (@impl $generics:tt [#[same_arity($count:expr)] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident $first:tt) => {
+        $crate::bijection!(@same_arity ($count) $first);
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref $first);
+    };
+    (@impl $generics:tt [#[same_arity $($unknown:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[same_arity] needs the expected number of variants, e.g. #[same_arity(3)]");
+    };
+
+    (@same_arity ($count:expr)
+        { $kind:tt $src_ty:ty => $dst_ty:ty { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* } }
+    ) => {
+        #[allow(unused_doc_comments)]
+        const _: () = ::core::assert!(
+            [$($(#[$($attr)*])* stringify!($pat)),*].len() == $count,
+            "The number of bijection branches doesn't match #[same_arity]"
+        );
+    };
+
+    // Ex: try_bijection!(#[fallback(foo_to_bar, bar_to_foo)] Foo, Bar, { ... })
+    // The catch-all of a fallible `match` delegates to the function instead, through a last branch.
+    // Its result is unwrapped with `?` (as the branches are wrapped in `Ok`), which also converts the error.
+    // A `_` skips a direction, e.g. the infallible forward one of integer bijections.
+    (@impl $generics:tt [#[fallback(_, $reverse:path $(,)?)] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $first:tt
+        { (try_from $error_ty:ty) $src_ty:ty => $dst_ty:ty { $($arms:tt)* } }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $first
+            { (try_from $error_ty) $src_ty => $dst_ty {
+                $($arms)*
+                #[allow(unreachable_patterns)]
+                other => $reverse(other)?,
+            } }
+            $($rest)*
+        );
+    };
+    (@impl $generics:tt [#[fallback($forward:path, $reverse:path $(,)?)] $($flag:tt)*] $($rest:tt)*) => {
+        $crate::bijection!(@impl $generics [#[fallback($forward)] #[fallback(_, $reverse)] $($flag)*] $($rest)*);
+    };
+    (@impl $generics:tt [#[fallback($forward:path $(,)?)] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { (try_from $error_ty:ty) $src_ty:ty => $dst_ty:ty { $($arms:tt)* } }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { (try_from $error_ty) $src_ty => $dst_ty {
+                $($arms)*
+                #[allow(unreachable_patterns)]
+                other => $forward(other)?,
+            } }
+            $($rest)*
+        );
+    };
+    (@impl $generics:tt [#[fallback $($args:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[fallback] needs a fallible conversion in each direction it's given for, e.g. try_bijection!(#[fallback(foo_to_bar, bar_to_foo)] Foo, Bar, { ... })");
+    };
+
+    // Ex: bijection!(#[repr_cast] Op, u8, { ... })
+    // The forward conversion (the first group) becomes a cast, after checking each branch against the discriminants.
+    // Both groups have a `match` branch for each bijection branch, so they can be zipped for the checks.
+    (@impl $generics:tt [#[repr_cast] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        { from $src_ty:ty => $dst_ty:ty { $($first:tt)* } }
+        { $second_kind:tt $($second:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@repr_cast_check $dst_ty { $($first)* } $($second)*);
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            { cast $src_ty => $dst_ty { $($first)* } }
+            { $second_kind $($second)* }
+            $($rest)*
+        );
+    };
+    (@impl $generics:tt [#[repr_cast] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[repr_cast] is only supported for bijections with a primitive integer, e.g. bijection!(#[repr_cast] Op, u8, { ... })");
+    };
+
+    (@repr_cast_check $int_ty:ty
+        { $($(#[$($first_attr:tt)*])* $first_pat:pat $(if $first_guard:expr)? => $first_expr:expr,)* }
+        $second_src_ty:ty => $second_dst_ty:ty
+        { $($(#[$($second_attr:tt)*])* $second_pat:pat $(if $second_guard:expr)? => $second_expr:expr,)* }
+    ) => {
+        #[allow(unused_doc_comments)]
+        const _: () = {
+            $(
+                $(#[$($second_attr)*])*
+                ::core::assert!(
+                    $second_expr as $int_ty == $first_expr,
+                    concat!("#[repr_cast] needs the discriminant of ", stringify!($first_pat), " to be ", stringify!($second_pat)),
+                );
+            )*
+        };
+    };
+
+    // Ex: bijection!(#[table] Op, u32, { ... })
+    // Only the fallible reverse (the second group) of integer bijections is looked up in a table
+    (@impl $generics:tt [#[table] $($flag:tt)*] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $first:tt
+        { (try_from $error_ty:ty) $($second:tt)* }
+        $($rest:tt)*
+    ) => {
+        $crate::bijection!(@impl $generics [$($flag)*] $fn_attrs $impl_attrs $by_ref
+            $first
+            { (table $error_ty) $($second)* }
+            $($rest)*
+        );
+    };
+
+    (@impl $generics:tt [#[table] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("#[table] is only supported for bijections with a primitive integer or `char`, e.g. bijection!(#[table] Op, u32, { ... })");
+    };
+
+    // `Vec`, `Option` and `Arc` are not fundamental types (unlike `Box`), so such impls would break the orphan rule
+    (@impl $generics:tt [#[vec] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Collection conversions can't be generated: From<Vec<Foo>> for Vec<Bar> is not allowed by the orphan rule, use .into_iter().map(Into::into).collect() instead");
+    };
+    (@impl $generics:tt [#[option] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Option conversions can't be generated: From<Option<Foo>> for Option<Bar> is not allowed by the orphan rule, use .map(Into::into) instead");
+    };
+    // Any `From` impl comes with a blanket `TryFrom` impl, which a second one would conflict with
+    (@impl $generics:tt [#[also_tryfrom $($error:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("TryFrom can't be implemented next to From: core already implements TryFrom<Foo> for Bar (with Infallible) for every From<Foo> for Bar, use try_bijection! with the error type instead");
+    };
+
+    (@impl $generics:tt [#[arc] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Arc conversions can't be generated: From<Arc<Foo>> for Arc<Bar> is not allowed by the orphan rule, use Arc::new(Bar::from((*foo).clone())) instead");
+    };
+    (@impl $generics:tt [#[rc] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Rc conversions can't be generated: From<Rc<Foo>> for Rc<Bar> is not allowed by the orphan rule, use Rc::new(Bar::from((*foo).clone())) instead");
+    };
+    (@impl $generics:tt [#[map_values] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!("Map conversions can't be generated: From<HashMap<K, Foo>> for HashMap<K, Bar> is not allowed by the orphan rule, use .into_iter().map(|(k, v)| (k, v.into())).collect() instead");
+    };
+
+    (@impl $generics:tt [#[$($unknown:tt)*] $($flag:tt)*] $($rest:tt)*) => {
+        compile_error!(concat!("Unknown bijection flag: #[", stringify!($($unknown)*), "]"));
+    };
+
+    // Impls, once all flags are applied
+    // Each impl is written by its own invocation, with the attributes passed along as whole groups
+    (@impl $generics:tt [] $fn_attrs:tt $impl_attrs:tt $by_ref:ident
+        $({ $kind:tt $src_ty:ty => $dst_ty:ty { $($arms:tt)* } })*
+    ) => {
+        $( $crate::bijection!(@impl_one $kind $generics $fn_attrs $impl_attrs $by_ref $src_ty => $dst_ty { $($arms)* }); )*
+    };
+
+    // A single From impl
+    // Like the output of `#[derive]`, every generated trait impl is marked `#[automatically_derived]`
+    // An empty block only compiles for empty types, so the matched value is wrapped
+    // to point the non-exhaustive error at the hint on the wrapper (see `__private::EmptyBijectionBlock`)
+    (@impl_one from ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } owned $src_ty:ty => $dst_ty:ty {}) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: $src_ty) -> Self {
+                match $crate::__private::EmptyBijectionBlock(value) {}
+            }
+        }
+    };
+    (@impl_one from ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } owned $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: $src_ty) -> Self {
+                match value {
+                    $($arms)*
+                }
+            }
+        }
+    };
+
+    (@impl_one from ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } by_ref $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::bijection!(@impl_one from ([$($generic)*] [$($pred)*]) { $($fn_attr)* } { $($impl_attr)* } owned $src_ty => $dst_ty { $($arms)* });
+
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<&$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: &$src_ty) -> Self {
+                match *value {
+                    $($arms)*
+                }
+            }
+        }
+    };
+
+    // A single From impl casting the discriminant (see `#[repr_cast]`)
+    (@impl_one cast ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } owned $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: $src_ty) -> Self {
+                value as $dst_ty
+            }
+        }
+    };
+
+    // The value can't be moved out of the reference to cast it, so the borrowing impl keeps the `match`
+    (@impl_one cast ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } by_ref $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $crate::bijection!(@impl_one cast ([$($generic)*] [$($pred)*]) { $($fn_attr)* } { $($impl_attr)* } owned $src_ty => $dst_ty { $($arms)* });
+
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<&$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: &$src_ty) -> Self {
+                match *value {
+                    $($arms)*
+                }
+            }
+        }
+    };
+
+    // Other flags only need the conversion itself, so the cast is the same as any other `From` impl to them
+    (@impl_one (eq cast) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (eq from) $($rest)*);
+    };
+    (@impl_one (boxed cast) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (boxed from) $($rest)*);
+    };
+    (@impl_one (borrow cast) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (borrow from) $($rest)*);
+    };
+    (@impl_one (by_mut cast) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (by_mut from) $($rest)*);
+    };
+    (@impl_one (const $method:tt cast) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (const $method from) $($rest)*);
+    };
+
+    // A single TryFrom impl
+    // Unmatched values fall through to a catch-all, which hands them over to the error type
+    (@impl_one (try_from $error_ty:ty) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } owned $src_ty:ty => $dst_ty:ty
+        { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* }
+    ) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::TryFrom<$src_ty> for $dst_ty where $($pred)* {
+            type Error = $error_ty;
+
+            $($fn_attr)*
+            fn try_from(value: $src_ty) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    $($(#[$($attr)*])* $pat $(if $guard)? => ::core::result::Result::Ok($expr),)*
+                    #[allow(unreachable_patterns)]
+                    other => ::core::result::Result::Err(::core::convert::From::from(other)),
+                }
+            }
+        }
+    };
+
+    // The unmatched value is copied out of the reference as well, so the source type must be `Copy`
+    (@impl_one (try_from $error_ty:ty) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } by_ref $src_ty:ty => $dst_ty:ty
+        { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* }
+    ) => {
+        $crate::bijection!(@impl_one (try_from $error_ty) ([$($generic)*] [$($pred)*]) { $($fn_attr)* } { $($impl_attr)* } owned $src_ty => $dst_ty
+            { $($(#[$($attr)*])* $pat $(if $guard)? => $expr,)* }
+        );
+
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::TryFrom<&$src_ty> for $dst_ty where $($pred)* {
+            type Error = $error_ty;
+
+            $($fn_attr)*
+            fn try_from(value: &$src_ty) -> ::core::result::Result<Self, Self::Error> {
+                match *value {
+                    $($(#[$($attr)*])* $pat $(if $guard)? => ::core::result::Result::Ok($expr),)*
+                    #[allow(unreachable_patterns)]
+                    other => ::core::result::Result::Err(::core::convert::From::from(other)),
+                }
+            }
+        }
+    };
+
+    // A single TryFrom impl, with a binary search instead of a `match` (see `#[table]`)
+    // The table is sorted by an insertion sort in the const initializer, so the branches can come in any order
+    (@impl_one (table $error_ty:ty) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } owned $src_ty:ty => $dst_ty:ty
+        { $($key:literal => $value:expr,)* }
+    ) => {
+        impl<$($generic)*> $dst_ty where $($pred)* {
+            /// The `(value, variant)` pairs of the bijection, sorted by the value
+            pub const LOOKUP: &'static [($src_ty, $dst_ty)] = &{
+                let mut table = [$(($key, $value)),*];
+                let mut i = 1;
+                while i < table.len() {
+                    let mut j = i;
+                    while j > 0 && table[j - 1].0 > table[j].0 {
+                        let (head, tail) = table.split_at_mut(j);
+                        ::core::mem::swap(&mut head[j - 1], &mut tail[0]);
+                        j -= 1;
+                    }
+                    i += 1;
+                }
+                table
+            };
+        }
+
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::TryFrom<$src_ty> for $dst_ty where $($pred)* {
+            type Error = $error_ty;
+
+            $($fn_attr)*
+            fn try_from(value: $src_ty) -> ::core::result::Result<Self, $error_ty> {
+                match <$dst_ty>::LOOKUP.binary_search_by(|(key, _)| ::core::cmp::Ord::cmp(key, &value)) {
+                    ::core::result::Result::Ok(i) => ::core::result::Result::Ok(::core::clone::Clone::clone(&<$dst_ty>::LOOKUP[i].1)),
+                    ::core::result::Result::Err(_) => ::core::result::Result::Err(::core::convert::From::from(value)),
+                }
+            }
+        }
+    };
+
+    (@impl_one (table $error_ty:ty) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } by_ref $src_ty:ty => $dst_ty:ty
+        { $($key:literal => $value:expr,)* }
+    ) => {
+        $crate::bijection!(@impl_one (table $error_ty) ([$($generic)*] [$($pred)*]) { $($fn_attr)* } { $($impl_attr)* } owned $src_ty => $dst_ty
+            { $($key => $value,)* }
+        );
+
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::TryFrom<&$src_ty> for $dst_ty where $($pred)* {
+            type Error = $error_ty;
+
+            $($fn_attr)*
+            fn try_from(value: &$src_ty) -> ::core::result::Result<Self, $error_ty> {
+                <$dst_ty as ::core::convert::TryFrom<$src_ty>>::try_from(*value)
+            }
+        }
+    };
+
+    (@impl_one (table $error_ty:ty) $($rest:tt)*) => {
+        compile_error!("#[table] needs every branch to map a unit variant to a literal, e.g. Op::Add => 0");
+    };
+
+    // The other impls only call the conversion, so they don't care whether it's a table
+    (@impl_one (eq (table $error_ty:ty)) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (eq (try_from $error_ty)) $($rest)*);
+    };
+    (@impl_one (boxed (table $error_ty:ty)) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (boxed (try_from $error_ty)) $($rest)*);
+    };
+    (@impl_one (borrow (table $error_ty:ty)) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (borrow (try_from $error_ty)) $($rest)*);
+    };
+    (@impl_one (const $method:tt (table $error_ty:ty)) $($rest:tt)*) => {
+        $crate::bijection!(@impl_one (const $method (try_from $error_ty)) $($rest)*);
+    };
+
+    // A single borrowing From impl
+    // Each pattern is matched through the reference (`&Foo::B(x)`), so bindings are copied out of it, like with `#[by_ref]`
+    (@impl_one (borrow from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } $by_ref:ident $src_ty:ty => $dst_ty:ty
+        { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* }
+    ) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<&$src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: &$src_ty) -> Self {
+                match value {
+                    $($(#[$($attr)*])* &$pat $(if $guard)? => $expr,)*
+                }
+            }
+        }
+    };
+
+    (@impl_one (borrow (try_from $error_ty:ty)) $($rest:tt)*) => {
+        compile_error!("Borrowing conversions can't be fallible");
+    };
+
+    // A single boxed From impl, which unboxes the value, converts it, and boxes the result again
+    // These take the box by value, so `#[by_ref]` doesn't apply here
+    (@impl_one (boxed from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } $by_ref:ident $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<$crate::__private::Box<$src_ty>> for $crate::__private::Box<$dst_ty> where $($pred)* {
+            $($fn_attr)*
+            fn from(value: $crate::__private::Box<$src_ty>) -> Self {
+                $crate::__private::Box::new(<$dst_ty as ::core::convert::From<$src_ty>>::from(*value))
+            }
+        }
+    };
+
+    (@impl_one (boxed (try_from $error_ty:ty)) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } $by_ref:ident $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::TryFrom<$crate::__private::Box<$src_ty>> for $crate::__private::Box<$dst_ty> where $($pred)* {
+            type Error = $error_ty;
+
+            $($fn_attr)*
+            fn try_from(value: $crate::__private::Box<$src_ty>) -> ::core::result::Result<Self, Self::Error> {
+                <$dst_ty as ::core::convert::TryFrom<$src_ty>>::try_from(*value).map($crate::__private::Box::new)
+            }
+        }
+    };
+
+    // A single From impl through a mutable reference, which takes the value and leaves its default behind
+    (@impl_one (by_mut from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } { $($impl_attr:tt)* } $by_ref:ident $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        $($impl_attr)*
+        #[automatically_derived]
+        impl<$($generic)*> ::core::convert::From<&mut $src_ty> for $dst_ty where $($pred)* {
+            $($fn_attr)*
+            fn from(value: &mut $src_ty) -> Self {
+                <$dst_ty as ::core::convert::From<$src_ty>>::from(::core::mem::take(value))
+            }
+        }
+    };
+
+    (@impl_one (by_mut (try_from $error_ty:ty)) $($rest:tt)*) => {
+        compile_error!("#[by_mut] can't be used with fallible conversions, as the value would be taken even if the conversion fails");
+    };
+    (@impl_one (by_mut (table $error_ty:ty)) $($rest:tt)*) => {
+        compile_error!("#[by_mut] can't be used with fallible conversions, as the value would be taken even if the conversion fails");
+    };
+
+    // Other flags duplicating the groups (e.g. `#[eq]` after `#[by_mut]`) already cover the owned conversions
+    (@impl_one (by_mut $kind:tt) $($rest:tt)*) => {};
+    (@impl_one (eq (by_mut $kind:tt)) $($rest:tt)*) => {};
+    (@impl_one (boxed (by_mut $kind:tt)) $($rest:tt)*) => {};
+
+    // A single const conversion method
+    // Only unit variants (and literals) are allowed, which is checked on the stringified patterns,
+    // like the duplicate checks below. Checking both directions covers both sides of each branch.
+    (@impl_one (const [$vis:vis $name:ident] from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } $impl_attrs:tt $by_ref:ident $src_ty:ty => $dst_ty:ty
+        { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr,)* }
+    ) => {
+        impl<$($generic)*> $src_ty where $($pred)* {
+            $($fn_attr)*
+            $vis const fn $name(self) -> $dst_ty {
+                match self {
+                    $($(#[$($attr)*])* $pat $(if $guard)? => $expr,)*
+                }
+            }
+        }
+
+        const _: () = {
+            const PATTERNS: &[&str] = &[$(stringify!($pat)),*];
+            const FOUND: ::core::option::Option<usize> = $crate::__private::find_group(PATTERNS);
+            const PARTS: &[&str] = &[
+                "Const conversions only support unit variants, found:\n",
+                match FOUND {
+                    ::core::option::Option::Some(i) => PATTERNS[i],
+                    ::core::option::Option::None => "",
+                },
+            ];
+            const MESSAGE: [u8; $crate::__private::message_len(PARTS)] = $crate::__private::message(PARTS);
+
+            if FOUND.is_some() {
+                ::core::panic!("{}", $crate::__private::as_str(&MESSAGE));
+            }
+        };
+    };
+
+    (@impl_one (const $method:tt (try_from $error_ty:ty)) $($rest:tt)*) => {
+        compile_error!("Const conversions can't be fallible");
+    };
+
+    // A single PartialEq impl
+    // The left-hand side is converted, and compared to the right-hand side.
+    // The impl attributes describe the conversion, so they are left out here.
+    (@impl_one (eq from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } $impl_attrs:tt owned $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        #[automatically_derived]
+        impl<$($generic)*> ::core::cmp::PartialEq<$dst_ty> for $src_ty
+        where
+            $src_ty: ::core::clone::Clone,
+            $dst_ty: ::core::cmp::PartialEq,
+            $($pred)*
+        {
+            $($fn_attr)*
+            fn eq(&self, other: &$dst_ty) -> bool {
+                let converted = <$dst_ty as ::core::convert::From<$src_ty>>::from(::core::clone::Clone::clone(self));
+                converted == *other
+            }
+        }
+    };
+
+    // No clone is needed when converting by reference
+    (@impl_one (eq from) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } $impl_attrs:tt by_ref $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        #[automatically_derived]
+        impl<$($generic)*> ::core::cmp::PartialEq<$dst_ty> for $src_ty
+        where
+            $dst_ty: ::core::cmp::PartialEq,
+            $($pred)*
+        {
+            $($fn_attr)*
+            fn eq(&self, other: &$dst_ty) -> bool {
+                let converted = <$dst_ty as ::core::convert::From<&$src_ty>>::from(self);
+                converted == *other
+            }
+        }
+    };
+
+    // Values which fail to convert are never equal
+    (@impl_one (eq (try_from $error_ty:ty)) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } $impl_attrs:tt owned $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        #[automatically_derived]
+        impl<$($generic)*> ::core::cmp::PartialEq<$dst_ty> for $src_ty
+        where
+            $src_ty: ::core::clone::Clone,
+            $dst_ty: ::core::cmp::PartialEq,
+            $($pred)*
+        {
+            $($fn_attr)*
+            fn eq(&self, other: &$dst_ty) -> bool {
+                match <$dst_ty as ::core::convert::TryFrom<$src_ty>>::try_from(::core::clone::Clone::clone(self)) {
+                    ::core::result::Result::Ok(value) => value == *other,
+                    ::core::result::Result::Err(_) => false,
+                }
+            }
+        }
+    };
+
+    (@impl_one (eq (try_from $error_ty:ty)) ([$($generic:tt)*] [$($pred:tt)*]) { $($fn_attr:tt)* } $impl_attrs:tt by_ref $src_ty:ty => $dst_ty:ty { $($arms:tt)* }) => {
+        #[automatically_derived]
+        impl<$($generic)*> ::core::cmp::PartialEq<$dst_ty> for $src_ty
+        where
+            $dst_ty: ::core::cmp::PartialEq,
+            $($pred)*
+        {
+            $($fn_attr)*
+            fn eq(&self, other: &$dst_ty) -> bool {
+                match <$dst_ty as ::core::convert::TryFrom<&$src_ty>>::try_from(self) {
+                    ::core::result::Result::Ok(value) => value == *other,
+                    ::core::result::Result::Err(_) => false,
+                }
+            }
+        }
+    };
+
+    // Entry (declaration block first)
+    // Ex: bijection!({ ... } for #[by_ref] Foo, Bar)
+    // Reordered into the usual form, which handles everything else (the types can't be followed by a tail here)
+    ({ $($bij:tt)* } for $($rest:tt)*) => {
+        $crate::bijection!($($rest)*, { $($bij)* });
+    };
+
+    // Entry (in a module)
+    // Ex: bijection!(pub mod conv; Foo, Bar, { ... })
+    // This goes first, as the other entries would fail to parse `mod` as a type.
+    // The visibility is matched by hand, as a `vis` fragment (which may be empty) would be ambiguous after the attributes.
+    ($(#[$($attr:tt)*])* $(pub $(($($restriction:tt)*))?)? mod $name:ident; $($rest:tt)*) => {
+        $(#[$($attr)*])*
+        $(pub $(($($restriction)*))?)? mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::bijection!($($rest)*);
+        }
+    };
+
+    // Entry (with generics)
+    // Ex: bijection!(<T, U: Default> Foo<T, U>, Bar<T, U>, { ... })
+    // This can't be an optional part of the entries below, as `<` may also start a type
+    ($(#[$($flag:tt)*])* < $($param:tt $(: $lifetime_bound:lifetime)? $(: $bound:path)?),* $(,)? > $($rest:tt)*) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([$($param $(: $lifetime_bound)? $(: $bound)?),*] []) $($rest)*);
+    };
+
+    // Entry (string keys)
+    // Ex: bijection!(&'static str, Cmd, { "go" => Cmd::Go, "stop" => Cmd::Stop })
+    // Not every string has a variant, so this is handled like integers, with the fallible direction from the strings.
+    // The impls are generic over the lifetime, so any `&str` can be looked up (and the literals are returned as such).
+    // These go before the borrowing entries, which would take the `&` for a borrowed side.
+    ($(#[$($flag:tt)*])* & $($lifetime:lifetime)? str, $second_ty:ty, $(error = $error_ty:ty,)?
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@str_key ($($error_ty)?) (str_key) [$(#[$($flag)*])*] &'s str, $second_ty, { $($bij)* });
+        $crate::bijection!(@tail ([] []) ($second_ty, &'static str) $($tail)*);
+    };
+    ($(#[$($flag:tt)*])* $first_ty:ty, & $($lifetime:lifetime)? str, $(error = $error_ty:ty,)?
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@str_key ($($error_ty)?) (integer) [$(#[$($flag)*])*] $first_ty, &'s str, { $($bij)* });
+        $crate::bijection!(@tail ([] []) ($first_ty, &'static str) $($tail)*);
+    };
+
+    // Without an error type, the unknown string itself is returned
+    (@str_key () $mode:tt $($rest:tt)*) => {
+        $crate::bijection!(@str_key (&'s str) $mode $($rest)*);
+    };
+    (@str_key ($error_ty:ty) ($mode:ident) $flags:tt $first_ty:ty, $second_ty:ty, {$($bij:tt)*}) => {
+        $crate::bijection!(@
+            ($flags (['s] []) $first_ty, $second_ty, $mode $error_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Entry (borrowing one side)
+    // Ex: bijection!(&Foo, Bar, { ... }) or bijection!(Foo, &Bar, { ... })
+    // The `&` can't be recognized once the type is captured, so this goes before the other entries
+    ($(#[$($flag:tt)*])* & $($rest:tt)*) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) & $($rest)*);
+    };
+    ($(#[$($flag:tt)*])* $first_ty:ty, & $($rest:tt)*) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) $first_ty, & $($rest)*);
+    };
+
+    // Entry (integer, with an error type)
+    // Ex: bijection!(Op, u8, error = OpError, { Op::Add => 0, Op::Sub => 1 })
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ident, error = $error_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@integer $second_ty ($error_ty) [$(#[$($flag)*])*] $first_ty, { $($bij)* } $($tail)*);
+    };
+
+    // Entry (integer)
+    // Ex: bijection!(Op, u8, { Op::Add => 0, Op::Sub => 1 })
+    // The second type must be matched as an ident to be recognized, as a captured `ty` is opaque
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ident,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@integer $second_ty () [$(#[$($flag)*])*] $first_ty, { $($bij)* } $($tail)*);
+    };
+
+    (@integer u8 $($rest:tt)*) => { $crate::bijection!(@integer_entry u8 $($rest)*); };
+    (@integer u16 $($rest:tt)*) => { $crate::bijection!(@integer_entry u16 $($rest)*); };
+    (@integer u32 $($rest:tt)*) => { $crate::bijection!(@integer_entry u32 $($rest)*); };
+    (@integer u64 $($rest:tt)*) => { $crate::bijection!(@integer_entry u64 $($rest)*); };
+    (@integer u128 $($rest:tt)*) => { $crate::bijection!(@integer_entry u128 $($rest)*); };
+    (@integer usize $($rest:tt)*) => { $crate::bijection!(@integer_entry usize $($rest)*); };
+    (@integer i8 $($rest:tt)*) => { $crate::bijection!(@integer_entry i8 $($rest)*); };
+    (@integer i16 $($rest:tt)*) => { $crate::bijection!(@integer_entry i16 $($rest)*); };
+    (@integer i32 $($rest:tt)*) => { $crate::bijection!(@integer_entry i32 $($rest)*); };
+    (@integer i64 $($rest:tt)*) => { $crate::bijection!(@integer_entry i64 $($rest)*); };
+    (@integer i128 $($rest:tt)*) => { $crate::bijection!(@integer_entry i128 $($rest)*); };
+    (@integer isize $($rest:tt)*) => { $crate::bijection!(@integer_entry isize $($rest)*); };
+
+    // Characters are handled like integers (e.g. Token::Plus => '+')
+    (@integer char $($rest:tt)*) => { $crate::bijection!(@integer_entry char $($rest)*); };
+
+    // Other types which can't be matched exhaustively, each value would need its own branch
+    (@integer f32 $($rest:tt)*) => { $crate::bijection!(@open_type f32 $($rest)*); };
+    (@integer f64 $($rest:tt)*) => { $crate::bijection!(@open_type f64 $($rest)*); };
+    (@integer String $($rest:tt)*) => { $crate::bijection!(@open_type String $($rest)*); };
+
+    // Only rejected if every branch maps to a literal, a binding (e.g. Meters(m) => m) may still cover the rest
+    (@open_type $second_ty:ident () $flags:tt $first_ty:ty, {
+        $(
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            => $rhs_lit:literal
+        ),+
+        $(,)?
+    } $($tail:tt)*) => {
+        compile_error!(concat!(
+            "Not every `", stringify!($second_ty), "` has a counterpart, so the reverse conversion can't be a `From` impl; ",
+            "use `try_bijection!` to generate `TryFrom` impls instead"
+        ));
+    };
+    (@open_type $second_ty:ident () $flags:tt $first_ty:ty, $($rest:tt)*) => {
+        $crate::bijection!(@entry $flags ([] []) $first_ty, $second_ty, $($rest)*);
+    };
+    (@open_type $second_ty:ident ($error_ty:ty) $($rest:tt)*) => {
+        $crate::bijection!(@integer_entry $second_ty ($error_ty) $($rest)*);
+    };
+
+    // `bool` is matched exhaustively with a branch for each value, so it's only rejected if every branch maps to the same one
+    // Ex: bijection!(Toggle, bool, { Toggle::On => true }), where `false` would have no counterpart
+    (@integer bool () $flags:tt $first_ty:ty, {
+        $(
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            => true
+        ),+
+        $(,)?
+    } $($tail:tt)*) => {
+        $crate::bijection!(@one_sided_bool true false);
+    };
+    (@integer bool () $flags:tt $first_ty:ty, {
+        $(
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            => false
+        ),+
+        $(,)?
+    } $($tail:tt)*) => {
+        $crate::bijection!(@one_sided_bool false true);
+    };
+    (@one_sided_bool $covered:literal $missing:literal) => {
+        compile_error!(concat!(
+            "Every branch maps to `", stringify!($covered), "`, so `", stringify!($missing), "` has no counterpart ",
+            "and the reverse conversion can't be a `From` impl; ",
+            "add a branch for `", stringify!($missing), "`, or use `try_bijection!` to generate `TryFrom` impls instead"
+        ));
+    };
+
+    // Not an integer, continue as usual
+    (@integer $second_ty:ident () $flags:tt $first_ty:ty, $($rest:tt)*) => {
+        $crate::bijection!(@entry $flags ([] []) $first_ty, $second_ty, $($rest)*);
+    };
+    // With an error type, any other type gets the same fallible reverse (see the entry below)
+    (@integer $second_ty:ident ($error_ty:ty) $($rest:tt)*) => {
+        $crate::bijection!(@integer_entry $second_ty ($error_ty) $($rest)*);
+    };
+
+    // Without an error type, the reverse is only fallible if every branch maps to a literal,
+    // and the unknown integer itself is returned. A binding (e.g. Wrapper(x) => x) covers every value instead.
+    // The branches are read on a copy of the block, and passed on as written.
+    // An explicit reverse is read as a branch of its own, with `reverse` as its path.
+    (@integer_entry $second_ty:ident () $flags:tt $first_ty:ty, {$($bij:tt)*} $($tail:tt)*) => {
+        $crate::bijection!(@integer_branches $second_ty { $($bij)* } ($flags $first_ty, { $($bij)* } $($tail)*));
+    };
+    (@integer_branches $second_ty:ident {
+        $(
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::+ $($lhs_lit:literal)?
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            =>
+            $rhs_first:tt $($rhs_lit:literal)? $(:: < $($rhs_first_gen:ty),* >)?
+            $(:: $rhs_seg:ident $(:: < $($rhs_gen:ty),* >)?)*
+            $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            $(,)? $(;)?
+        )+
+    } $rest:tt) => {
+        $crate::bijection!(@integer_reverse $second_ty [
+            $((
+                [
+                    $rhs_first $($rhs_lit)? $(:: $rhs_seg)*
+                    $(( $($rhs_paren)* ))? $({ $($rhs_brace)* })? $([ $($rhs_bracket)* ])?
+                ]
+                $(l $lhs_lit)?
+                [$($lhs_seg)::+]
+            ))+
+        ] $rest);
+    };
+    // Other branches (e.g. or-patterns) are left to the arms reading them, with the fallible reverse
+    (@integer_branches $second_ty:ident $block:tt ($($rest:tt)*)) => {
+        $crate::bijection!(@integer_entry $second_ty ($second_ty) $($rest)*);
+    };
+
+    // The branches mapping to a literal are skipped all at once, up to the next one which doesn't
+    (@integer_reverse $second_ty:ident [$(([$rhs_lit:literal] [$($lhs:tt)*]))*] ($($rest:tt)*)) => {
+        $crate::bijection!(@integer_entry $second_ty ($second_ty) $($rest)*);
+    };
+    // An explicit reverse from a literal (e.g. Op::Raw(n) => n; reverse 0x80 => Op::Raw(0x80))
+    (@integer_reverse $second_ty:ident [
+        $(([$rhs_lit:literal] [$($lhs:tt)*]))*
+        ([$reverse_first:ident $($reverse_rhs:tt)*] l $reverse_lit:literal [reverse])
+        $($branches:tt)*
+    ] $rest:tt) => {
+        $crate::bijection!(@integer_reverse $second_ty [$($branches)*] $rest);
+    };
+    (@integer_reverse $second_ty:ident [
+        $(([$rhs_lit:literal] [$($lhs:tt)*]))*
+        ([$branch_first:ident $($branch_rhs:tt)*] [$($branch_lhs:tt)*])
+        ([$reverse_first:ident $($reverse_rhs:tt)*] l $reverse_lit:literal [reverse])
+        $($branches:tt)*
+    ] $rest:tt) => {
+        $crate::bijection!(@integer_reverse $second_ty [$($branches)*] $rest);
+    };
+    // A binding without an explicit reverse, so every value has a counterpart
+    (@integer_reverse $second_ty:ident [
+        $(([$rhs_lit:literal] [$($lhs:tt)*]))*
+        ([$binding:ident] [$($branch_lhs:tt)*])
+        $($branches:tt)*
+    ] ($flags:tt $($rest:tt)*)) => {
+        $crate::bijection!(@integer_binding $second_ty $flags ($flags $($rest)*));
+    };
+    // `#[table]` only looks up the fallible reverse, and rejects the binding there
+    (@integer_binding $second_ty:ident [#[table] $($flag:tt)*] ($($rest:tt)*)) => {
+        $crate::bijection!(@integer_entry $second_ty ($second_ty) $($rest)*);
+    };
+    (@integer_binding $second_ty:ident [#[$($other:tt)*] $($flag:tt)*] $rest:tt) => {
+        $crate::bijection!(@integer_binding $second_ty [$($flag)*] $rest);
+    };
+    (@integer_binding $second_ty:ident [] ($flags:tt $first_ty:ty, $($rest:tt)*)) => {
+        $crate::bijection!(@entry $flags ([] []) $first_ty, $second_ty, $($rest)*);
+    };
+    // A path (e.g. a constant like u8::MAX) is matched like a literal
+    (@integer_reverse $second_ty:ident [
+        $(([$rhs_lit:literal] [$($lhs:tt)*]))*
+        ([$branch_first:ident $($branch_rhs:tt)*] $($branch_lhs:tt)*)
+        $($branches:tt)*
+    ] $rest:tt) => {
+        $crate::bijection!(@integer_reverse $second_ty [$($branches)*] $rest);
+    };
+    (@integer_reverse $second_ty:ident $branches:tt ($($rest:tt)*)) => {
+        $crate::bijection!(@integer_entry $second_ty ($second_ty) $($rest)*);
+    };
+    (@integer_entry $second_ty:ident ($error_ty:ty) $flags:tt $first_ty:ty, {$($bij:tt)*} $($tail:tt)*) => {
+        $crate::bijection!(@
+            ($flags ([] []) $first_ty, $second_ty, integer $error_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+        $crate::bijection!(@tail ([] []) ($first_ty, $second_ty) $($tail)*);
+    };
+
+    // Entry (any other type, with an error type)
+    // Ex: bijection!(Flag, Flags, error = Flags, { Flag::A => Flags::A, Flag::B => Flags::B })
+    // The reverse is fallible like with integers, for types which can't be matched exhaustively (e.g. by associated consts).
+    // Second types written as a single identifier are handled by the integer entry above.
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty, error = $error_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@
+            ([$(#[$($flag)*])*] ([] []) $first_ty, $second_ty, integer $error_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+        $crate::bijection!(@tail ([] []) ($first_ty, $second_ty) $($tail)*);
+    };
+
+    // Entry
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) $first_ty, $second_ty, { $($bij)* } $($tail)*);
+    };
+
+    // Entry (forward only)
+    ($(#[$($flag:tt)*])* $first_ty:ty => $second_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) $first_ty => $second_ty, { $($bij)* } $($tail)*);
+    };
+
+    // Entry (with a where clause)
+    // Ex: bijection!(<T> Foo<T>, Bar<T> where T: Clone, { ... })
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty where $($rest:tt)*) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) $first_ty, $second_ty where $($rest)*);
+    };
+
+    ($(#[$($flag:tt)*])* $first_ty:ty => $second_ty:ty where $($rest:tt)*) => {
+        $crate::bijection!(@entry [$(#[$($flag)*])*] ([] []) $first_ty => $second_ty where $($rest)*);
+    };
+
+    // A leading `&` is handled separately, as it may also start a forward-only reference type
+    (@entry $flags:tt $generics:tt & $($rest:tt)*) => {
+        $crate::bijection!(@borrowed $flags $generics & $($rest)*);
+    };
+
+    (@entry $flags:tt $generics:tt $first_ty:ty, & $lifetime:lifetime $($rest:tt)*) => {
+        compile_error!("Borrowed bijection sides can't name a lifetime, write `&Foo` instead");
+    };
+
+    (@entry [$($flag:tt)*] $generics:tt $first_ty:ty, & $second_ty:ty $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@entry [$($flag)* #[@borrow_second]] $generics $first_ty, $second_ty $(, $($rest)*)?);
+    };
+
+    (@entry [$($flag:tt)*] $generics:tt $first_ty:ty, & $second_ty:ty where $($rest:tt)*) => {
+        $crate::bijection!(@entry [$($flag)* #[@borrow_second]] $generics $first_ty, $second_ty where $($rest)*);
+    };
+
+    // Ex: bijection!(<'a> &'a Foo => BarView<'a>, { ... })
+    // A reference type of its own, the captured type doesn't start with `&` anymore
+    (@borrowed $flags:tt $generics:tt $first_ty:ty => $($rest:tt)*) => {
+        $crate::bijection!(@entry $flags $generics $first_ty => $($rest)*);
+    };
+
+    (@borrowed $flags:tt $generics:tt & $lifetime:lifetime $($rest:tt)*) => {
+        compile_error!("Borrowed bijection sides can't name a lifetime, write `&Foo` instead");
+    };
+
+    // Without generics, this goes through the entries again (e.g. to recognize integers)
+    (@borrowed [$(#[$($flag:tt)*])*] ([] []) & $first_ty:ty, $($rest:tt)*) => {
+        $crate::bijection!($(#[$($flag)*])* #[@borrow_first] $first_ty, $($rest)*);
+    };
+
+    (@borrowed [$($flag:tt)*] $generics:tt & $first_ty:ty, $($rest:tt)*) => {
+        $crate::bijection!(@entry [$($flag)* #[@borrow_first]] $generics $first_ty, $($rest)*);
+    };
+
+    // The where clause is munched token by token, until the declaration block is reached
+    (@entry $flags:tt ([$($generic:tt)*] []) $first_ty:ty, $second_ty:ty where $($rest:tt)*) => {
+        $crate::bijection!(@where $flags [$($generic)*] ($first_ty, $second_ty) [] $($rest)*);
+    };
+
+    (@entry $flags:tt ([$($generic:tt)*] []) $first_ty:ty => $second_ty:ty where $($rest:tt)*) => {
+        $crate::bijection!(@where $flags [$($generic)*] ($first_ty => $second_ty) [] $($rest)*);
+    };
+
+    (@where $flags:tt [$($generic:tt)*] ($($types:tt)*) [$($pred:tt)*] , {$($bij:tt)*} $($tail:tt)*) => {
+        $crate::bijection!(@entry $flags ([$($generic)*] [$($pred)*]) $($types)*, { $($bij)* } $($tail)*);
+    };
+
+    (@where $flags:tt $generics:tt $types:tt [$($pred:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@where $flags $generics $types [$($pred)* $token] $($rest)*);
+    };
+
+    (@where $flags:tt $generics:tt $types:tt [$($pred:tt)*]) => {
+        compile_error!(
+            concat!(
+                "Missing bijection declaration block after where clause (got: where ",
+                stringify!($($pred)*),
+                ")"
+            )
+        );
+    };
+
+    (@entry $flags:tt $generics:tt $first_ty:ty, $second_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@
+            ($flags $generics $first_ty, $second_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+        $crate::bijection!(@tail $generics ($first_ty, $second_ty) $($tail)*);
+    };
+
+    // The branches are never read in reverse, so they go straight into the `match` as written
+    // (unless `#[auto_into]` needs to convert their bindings)
+    (@entry $flags:tt $generics:tt $first_ty:ty => $second_ty:ty,
+        {$($bij:tt)*} $($tail:tt)*
+    ) => {
+        $crate::bijection!(@forward_flags $flags ($flags $generics $first_ty, $second_ty, forward) ($($bij)*));
+        $crate::bijection!(@tail $generics ($first_ty => $second_ty) $($tail)*);
+    };
+
+    // Imports are placed around the generated code first (see "Imports")
+    (@forward_flags $flags:tt $ctx:tt (use $($rest:tt)*)) => {
+        $crate::bijection!(@imports (@forward_flags $flags $ctx) [] $($rest)*);
+    };
+    (@forward_flags [#[auto_into] $($flag:tt)*] $ctx:tt ($($bij:tt)*)) => {
+        $crate::bijection!(@forward_into $ctx {} $($bij)*);
+    };
+    (@forward_flags [#[$($other:tt)*] $($flag:tt)*] $ctx:tt $bij:tt) => {
+        $crate::bijection!(@forward_flags [$($flag)*] $ctx $bij);
+    };
+    (@forward_flags [] $ctx:tt ($($bij:tt)*)) => {
+        $crate::bijection!(@ $ctx { $($bij)* } {} ());
+    };
+
+    // Ex: Foo::A(x) => Bar::X(x), becomes Foo::A(x) => Bar::X(Into::into(x)),
+    // Expressions are collected up to the next comma, blocks are left as they are.
+    (@forward_into $ctx:tt { $($done:tt)* }
+        $(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => { $($block:tt)* } $(, $($rest:tt)*)?
+    ) => {
+        $crate::bijection!(@forward_into $ctx
+            { $($done)* $(#[$($attr)*])* $pat $(if $guard)? => { $($block)* }, }
+            $($($rest)*)?
+        );
+    };
+    (@forward_into $ctx:tt { $($done:tt)* }
+        $(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $($rest:tt)*
+    ) => {
+        $crate::bijection!(@forward_into_expr $ctx { $($done)* $(#[$($attr)*])* $pat $(if $guard)? => } [] $($rest)*);
+    };
+    (@forward_into $ctx:tt $done:tt) => {
+        $crate::bijection!(@ $ctx $done {} ());
+    };
+
+    (@forward_into_expr $ctx:tt { $($done:tt)* } [$($expr:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@forward_into $ctx
+            { $($done)* $crate::bijection!(@strip_bindings into [] {} $($expr)*), }
+            $($($rest)*)?
+        );
+    };
+    (@forward_into_expr $ctx:tt $done:tt [$($expr:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@forward_into_expr $ctx $done [$($expr)* $token] $($rest)*);
+    };
+
+    // Anything else after the generics is handled like it would be without them
+    (@entry $flags:tt $generics:tt $($rest:tt)*) => {
+        $crate::bijection!($($rest)*);
+    };
+
+    // ===== Tokens after the declaration block =====
+
+    (@tail $generics:tt $types:tt) => {};
+
+    // Ex: bijection!(Foo, Bar, { ... } test_samples(foo_bar_roundtrip) = [Foo::A, Foo::B])
+    // Generates a test, which converts each sample there and back again.
+    // Item names are not hygienic, so a fixed name would collide with the test of any other bijection in the same module.
+    (@tail $generics:tt $types:tt test_samples = $samples:tt) => {
+        compile_error!("The roundtrip test needs a name, e.g. test_samples(foo_bar_roundtrip) = [...]");
+    };
+
+    // The samples are passed to a generic fn (using the bijection's generics), so their types can be inferred
+    (@tail
+        ([$($generic:tt)*] [$($pred:tt)*])
+        ($first_ty:ty, $second_ty:ty)
+        test_samples($name:ident) = [$($sample:expr),* $(,)?]
+    ) => {
+        #[cfg(test)]
+        #[test]
+        fn $name() {
+            fn roundtrip<$($generic)*>(sample: $first_ty)
+            where
+                $first_ty: ::core::cmp::PartialEq + ::core::fmt::Debug + ::core::clone::Clone,
+                <$first_ty as ::core::convert::TryFrom<$second_ty>>::Error: ::core::cmp::PartialEq + ::core::fmt::Debug,
+                $($pred)*
+            {
+                let there = <$second_ty as ::core::convert::From<$first_ty>>::from(::core::clone::Clone::clone(&sample));
+                // The reverse may be fallible (e.g. with integers), any `From` impl provides `TryFrom` as well
+                let back = <$first_ty as ::core::convert::TryFrom<$second_ty>>::try_from(there);
+                ::core::assert_eq!(back, ::core::result::Result::Ok(sample), "Bijection roundtrip failed");
+            }
+
+            $( roundtrip($sample); )*
+        }
+    };
+
+    (@tail $generics:tt ($first_ty:ty => $second_ty:ty) test_samples $($samples:tt)*) => {
+        compile_error!("Roundtrip test samples require a conversion in both directions");
+    };
+
+    (@tail $generics:tt $types:tt $($unexpected:tt)+) => {
+        compile_error!(
+            concat!(
+                "Unexpected tokens after bijection declaration block: ",
+                stringify!($($unexpected)+)
+            )
+        );
+    };
+
+    // ===== Imports =====
+    // Ex: use Foo::*; A => Bar::X, B => Bar::Y
+    // Leading `use` declarations would have to go into the body of every generated fn,
+    // so the whole expansion is put into an anonymous const block with them instead (impls inside still apply).
+    // The declaration is collected up to its `;`, and the rest of the block is handled as usual in there.
+    (@ $ctx:tt {} {} (use $($rest:tt)*)) => {
+        $crate::bijection!(@imports (@ $ctx {} {}) [] $($rest)*);
+    };
+    (@imports ($($call:tt)*) [$($tree:tt)*] ; $($rest:tt)*) => {
+        const _: () = {
+            use $($tree)*;
+            $crate::bijection!($($call)* ($($rest)*));
+        };
+    };
+    (@imports $call:tt [$($tree:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@imports $call [$($tree)* $token] $($rest)*);
+    };
+    (@imports $call:tt [$($tree:tt)*]) => {
+        compile_error!(concat!("Missing `;` after `use ", stringify!($($tree)*), "`"));
+    };
+
+    // ===== Forward-only branches =====
+    // Ex: forward_only { Foo::B => Bar::X, Foo::C => Bar::X }
+    // These only go into the forward `match`. The reverse one gets a disabled placeholder for each of them instead,
+    // so both groups still have a `match` branch for each bijection branch (see "Branch docs").
+    // This goes before any arm reading a pattern, which would fail to parse the block as a struct pattern.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (forward_only { $($(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $expr:expr),* $(,)? } $($rest:tt)*)
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* $($(#[$($attr)*])* $pat $(if $guard)? => $expr,)* }
+            { $($second_done)* $(#[cfg(any())] _ => ::core::unreachable!("{}", stringify!($pat)),)* }
+            ($($rest)*)
+        );
+    };
+
+    // ===== Bidirectional arrows =====
+    // Ex: Foo::A <=> Bar::X
+    // Only another spelling of `=>`, so the arrow is replaced and the branch goes through the arms below.
+    // A pattern fragment can't be followed by `<=>`, so the left-hand side is matched by its shape instead:
+    // a path with an optional group (like in the fast path above), or a literal.
+    // This goes before any arm reading an expression as well.
+    (@
+    $ctx:tt
+        $first_done:tt
+        $second_done:tt
+        (
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            <=> $($rest:tt)*
+        )
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            $first_done
+            $second_done
+            (
+                $(#[$($attr)*])*
+                $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                $(( $($lhs_paren)* ))? $({ $($lhs_brace)* })? $([ $($lhs_bracket)* ])?
+                => $($rest)*
+            )
+        );
+    };
+    (@ $ctx:tt $first_done:tt $second_done:tt ($(#[$($attr:tt)*])* $lhs:literal <=> $($rest:tt)*)) => {
+        $crate::bijection!(@ $ctx $first_done $second_done ($(#[$($attr)*])* $lhs => $($rest)*));
+    };
+    (@ $ctx:tt $first_done:tt $second_done:tt ($(#[$($attr:tt)*])* - $lhs:literal <=> $($rest:tt)*)) => {
+        $crate::bijection!(@ $ctx $first_done $second_done ($(#[$($attr)*])* - $lhs => $($rest)*));
+    };
+
+    // ===== Inverse functions =====
+    // Ex: Foo::Meters(m) => Bar::Millimeters(m_to_mm(m)) <=> mm_to_m
+    // The call can't be reversed as a pattern, so this is rewritten to an explicit reverse,
+    // where the binding is passed through the inverse function first:
+    // Foo::Meters(m) => Bar::Millimeters(m_to_mm(m)); reverse Bar::Millimeters(m) => { let m = mm_to_m(m); Foo::Meters(m) }
+    // These go before any arm reading an expression, as `<=>` would fail to parse as one.
+    // Ex: Foo::Timestamp(secs) => Bar::Time(Duration::from_secs(secs)) <=> |d| d.as_secs()
+    // With a closure as the inverse, whose body is inlined with the parameter bound to the field:
+    // ...; reverse Bar::Time(secs) => { let secs = { let d = secs; d.as_secs() }; Foo::Timestamp(secs) }
+    // This goes before the arm below, as `|` would fail to parse as a path.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            =>
+            $($rhs_seg:ident $(:: < $($rhs_gen:ty),* >)?)::* ( $($forward:ident)::+ ( $binding:ident ) )
+            <=> | $param:ident $(: $param_ty:ty)? | $inverse:expr
+            $(, $($rest:tt)*)? $(; $($semi_rest:tt)*)?
+        )
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            (
+                $(#[$($attr)*])*
+                $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                $(( $($lhs_paren)* ))? $({ $($lhs_brace)* })? $([ $($lhs_bracket)* ])?
+                =>
+                $($rhs_seg $(:: < $($rhs_gen),* >)?)::* ( $($forward)::+ ( $binding ) ) ;
+                reverse $($rhs_seg $(:: < $($rhs_gen),* >)?)::* ( $binding ) => {
+                    let $binding = {
+                        let $param $(: $param_ty)? = $binding;
+                        $inverse
+                    };
+                    $crate::bijection!(@as_expr $ctx
+                        $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                        $(( $($lhs_paren)* ))? $({ $($lhs_brace)* })? $([ $($lhs_bracket)* ])?
+                    )
+                }
+                $(, $($rest)*)? $(; $($semi_rest)*)?
+            )
+        );
+    };
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            =>
+            $($rhs_seg:ident $(:: < $($rhs_gen:ty),* >)?)::* ( $($forward:ident)::+ ( $binding:ident ) )
+            <=> $inverse:path
+            $(, $($rest:tt)*)? $(; $($semi_rest:tt)*)?
+        )
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            (
+                $(#[$($attr)*])*
+                $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                $(( $($lhs_paren)* ))? $({ $($lhs_brace)* })? $([ $($lhs_bracket)* ])?
+                =>
+                $($rhs_seg $(:: < $($rhs_gen),* >)?)::* ( $($forward)::+ ( $binding ) ) ;
+                reverse $($rhs_seg $(:: < $($rhs_gen),* >)?)::* ( $binding ) => {
+                    let $binding = $inverse($binding);
+                    $crate::bijection!(@as_expr $ctx
+                        $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                        $(( $($lhs_paren)* ))? $({ $($lhs_brace)* })? $([ $($lhs_bracket)* ])?
+                    )
+                }
+                $(, $($rest)*)? $(; $($semi_rest)*)?
+            )
+        );
+    };
+
+    // Ex: n => n as u8 <=> |x| x as i8
+    // A cast of the whole value, with a cast back as its inverse (e.g. reinterpreting the bits of an integer).
+    // The reverse binds the whole value as well: n => n as u8; reverse n => { let x = n; x as i8 }
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $binding:ident => $value:ident as $($cast_seg:ident)::+
+            <=> | $param:ident $(: $param_ty:ty)? | $inverse:expr
+            $(, $($rest:tt)*)? $(; $($semi_rest:tt)*)?
+        )
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            (
+                $(#[$($attr)*])*
+                $binding => $value as $($cast_seg)::+ ;
+                reverse $binding => {
+                    let $param $(: $param_ty)? = $binding;
+                    $inverse
+                }
+                $(, $($rest)*)? $(; $($semi_rest)*)?
+            )
+        );
+    };
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $binding:ident => $value:ident as $($cast_seg:ident)::+
+            <=> $inverse:path
+            $(, $($rest:tt)*)? $(; $($semi_rest:tt)*)?
+        )
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            (
+                $(#[$($attr)*])*
+                $binding => $value as $($cast_seg)::+ ;
+                reverse $binding => $inverse($binding)
+                $(, $($rest)*)? $(; $($semi_rest)*)?
+            )
+        );
+    };
+
+    // ===== Lossy branches =====
+    // Catch-all forward patterns with an explicit reverse (e.g. _ => Bar::Other; reverse Bar::Other => Foo::Other)
+    // Everything the pattern catches maps back to a single value, which `#[warn_lossy]` points out.
+    // Identifiers are assumed to be bindings, so this is only a heuristic (e.g. `None` is reported as well).
+    // The pattern is passed on as a captured fragment, so these arms don't match it again.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* _ $(if $guard:expr)? => $first_expr:expr ; reverse $($rest:tt)*)
+    ) => {
+        $crate::bijection!(@warn_lossy $ctx);
+        $crate::bijection!(@recapture
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            ($(#[$($attr)*])* _ $(if $guard)? => $first_expr ; reverse $($rest)*)
+        );
+    };
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* $binding:ident $(if $guard:expr)? => $first_expr:expr ; reverse $($rest:tt)*)
+    ) => {
+        $crate::bijection!(@warn_lossy $ctx);
+        $crate::bijection!(@recapture
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            ($(#[$($attr)*])* $binding $(if $guard)? => $first_expr ; reverse $($rest)*)
+        );
+    };
+
+    (@recapture
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* $first_pat:pat_param $(if $guard:expr)? => $($rest:tt)*)
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            ($(#[$($attr)*])* $first_pat $(if $guard)? => $($rest)*)
+        );
+    };
+
+    // Macros can't emit warnings, so a deprecated item is used instead
+    (@warn_lossy ([#[warn_lossy] $($flag:tt)*] $($ctx:tt)*)) => {
+        const _: () = {
+            #[deprecated(note = "Lossy bijection branch: a catch-all pattern only maps back from a single value")]
+            struct LossyBranch;
+            let _ = LossyBranch;
+        };
+    };
+    (@warn_lossy ([#[$($other:tt)*] $($flag:tt)*] $($ctx:tt)*)) => {
+        $crate::bijection!(@warn_lossy ([$($flag)*] $($ctx)*));
+    };
+    (@warn_lossy ([] $($ctx:tt)*)) => {};
+
+    // ===== Separators =====
+    // Branches may be separated by `,` or `;`, and a block ending a branch needs no separator at all,
+    // like in a `match`. A run of plain branches (see below) ends at the first `;`.
+
+    // Leftover separators (e.g. after a block, see below)
+    (@ $ctx:tt { $($first_done:tt)* } { $($second_done:tt)* } (, $($rest:tt)*)) => {
+        $crate::bijection!(@ $ctx { $($first_done)* } { $($second_done)* } ($($rest)*));
+    };
+    (@ $ctx:tt { $($first_done:tt)* } { $($second_done:tt)* } (; $($rest:tt)*)) => {
+        $crate::bijection!(@ $ctx { $($first_done)* } { $($second_done)* } ($($rest)*));
+    };
+
+    // ===== Branches =====
+    // The `match` arms must keep the tokens of each pattern as they were written.
+    // A rebuilt group (e.g. `Foo(1)` from `Foo( $($field)* )`) would belong to this crate instead,
+    // and rustc leaves out lints like `unreachable_patterns` for code of another crate's macros,
+    // which is how overlapping patterns are reported.
+    // A side can only be found by taking its groups apart, so the remaining tokens are copied first:
+    // the branches are matched by their shape on the first copy, and their tokens are taken from the second one
+    // (see "Zipped branches" below). Only the remaining tokens of the first copy are passed on.
+    (@ $ctx:tt { $($first_done:tt)* } { $($second_done:tt)* } ($($rest:tt)*)) => {
+        $crate::bijection!(@ $ctx { $($first_done)* } { $($second_done)* } ($($rest)*) [$($rest)*]);
+    };
+
+    // ===== Runs of plain branches =====
+    // Fast path: the remaining branches up to the end of the block or the next `;` are made of two plain sides
+    // (e.g. Foo::A(x) => Bar::X(x), Foo::B => 0). These are all added to both `match` statements in a single step,
+    // so large bijections don't run into the recursion limit.
+    // A side is a path with an optional group, and the right-hand side may be a literal as well (e.g. `-1`).
+    // The fields of the left-hand side are read as patterns, which stop at cast hints (see below).
+    // Generic arguments (e.g. Either::<L, R>::Left) are single tokens here.
+    // The trailing comma is matched by its own repetition, so it can be marked as well.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(
+                $(#[$($attr:tt)*])*
+                $(
+                    $lhs_first:ident $(:: < $lhs_first_gen:tt $(, $lhs_first_gens:tt)* >)?
+                    $(:: $lhs_seg:ident $(:: < $lhs_gen:tt $(, $lhs_gens:tt)* >)?)*
+                )?
+                $(( $($lhs_field:pat),* $(,)? ))?
+                $({ $($lhs_name:tt $($lhs_bind:ident)? $(: $lhs_value:pat)?),* $(,)? })?
+                $([ $($lhs_element:pat),* $(,)? ])?
+                =>
+                $rhs_first:tt $($rhs_lit:literal)? $(:: < $rhs_first_gen:tt $(, $rhs_first_gens:tt)* >)?
+                $(:: $rhs_seg:ident $(:: < $rhs_gen:tt $(, $rhs_gens:tt)* >)?)*
+                $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            ),+
+            $(, $(@ $trailing:tt)?)?
+            $(; $($rest:tt)*)?
+        )
+        [$($raw:tt)*]
+    ) => {
+        $crate::bijection!(@zip $ctx { $($first_done)* } { $($second_done)* } []
+            [
+                $(
+                    $((a #) (a [$($attr)*]))*
+                    $(
+                        (l $lhs_first) $((l ::) (l <) (l $lhs_first_gen) $((l ,) (l $lhs_first_gens))* (l >))?
+                        $((l ::) (l $lhs_seg) $((l ::) (l <) (l $lhs_gen) $((l ,) (l $lhs_gens))* (l >))?)*
+                    )?
+                    $((l ( $($lhs_field),* )))?
+                    $((l { $($lhs_name $($lhs_bind)? $(: $lhs_value)?),* }))?
+                    $((l [ $($lhs_element),* ]))?
+                    (e =>)
+                    (r $rhs_first) $((r $rhs_lit))? $((r ::) (r <) (r $rhs_first_gen) $((r ,) (r $rhs_first_gens))* (r >))?
+                    $((r ::) (r $rhs_seg) $((r ::) (r <) (r $rhs_gen) $((r ,) (r $rhs_gens))* (r >))?)*
+                    $((r ( $($rhs_paren)* )))? $((r { $($rhs_brace)* }))? $((r [ $($rhs_bracket)* ]))?
+                ),+
+                $(, $(@ $trailing)?)?
+                $(; $((x $rest))*)?
+            ]
+            [$($raw)*]
+        );
+    };
+
+    // ===== Cast hints =====
+    // Ex: Foo::N(x as u64, y) => Bar::N(x, y)
+    // Bindings of the left-hand side may be cast for the other side. The hint is left out of the pattern,
+    // and the binding is shadowed by its cast before the other side is built:
+    // Foo::N(x, y) => { let x = x as u64; Bar::N(x, y) }
+    // The reverse casts it back with `as _`, so the target is inferred from the field of the left-hand side:
+    // Bar::N(x, y) => { let x = x as _; Foo::N(x, y) }
+    // A pattern fragment can't contain the hint, so the left-hand side is matched by its shape instead:
+    // a path with an optional tuple or struct group of plain bindings, each with an optional hint.
+    // Other groups (literals, nested patterns, ...) fail this arm softly and go on to the arms below.
+    // A run stops at a hint (see above), and this goes before any other arm reading a pattern, which would fail to parse it.
+    // Only comma-separated branches are matched here, as `;` may be followed by an explicit reverse.
+    // A hinted branch is added with rebuilt patterns, one branch at a time.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $($lhs_seg:ident $(:: < $($lhs_gen:ty),* >)?)::*
+            $(( $($lhs_field:ident $(as $lhs_cast:ty)?),* $(,)? ))?
+            $({ $($lhs_name:ident $(: $lhs_bind:ident)? $(as $lhs_named_cast:ty)?),* $(,)? })?
+            =>
+            $($rhs_seg:ident $(:: < $($rhs_gen:ty),* >)?)::*
+            $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            $(, $($rest:tt)*)?
+        )
+        [$($raw:tt)*]
+    ) => {
+        $crate::bijection!(@cast_probe
+            [$($($($lhs_cast)?)*)? $($($($lhs_named_cast)?)*)?]
+            (@ $ctx { $($first_done)* } { $($second_done)* } ($($raw)*) [$($raw)*] unhinted)
+            {
+                $crate::bijection!(@reject_inner_or_in (
+                        $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                        $(( $($lhs_field $(as $lhs_cast)?),* ))?
+                        $({ $($lhs_name $(: $lhs_bind)? $(as $lhs_named_cast)?),* })?
+                        =>
+                        $($rhs_seg $(:: < $($rhs_gen),* >)?)::*
+                        $(( $($rhs_paren)* ))? $({ $($rhs_brace)* })? $([ $($rhs_bracket)* ])?
+                    )
+                    $(( $($rhs_paren)* ))? $({ $($rhs_brace)* })? $([ $($rhs_bracket)* ])?
+                );
+                $crate::bijection!(@
+                    $ctx
+                    {
+                        $($first_done)*
+                        $(#[$($attr)*])*
+                        $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                        $(( $($lhs_field),* ))? $({ $($lhs_name $(: $lhs_bind)?),* })?
+                        => $crate::bijection!(@cast_fields $ctx into []
+                            [
+                                $($(($lhs_field $(as $lhs_cast)?))*)?
+                                $($(($lhs_name $(: $lhs_bind)? $(as $lhs_named_cast)?))*)?
+                            ]
+                            $($rhs_seg $(:: < $($rhs_gen),* >)?)::*
+                            $(( $($rhs_paren)* ))? $({ $($rhs_brace)* })? $([ $($rhs_bracket)* ])?
+                        ),
+                    }
+                    {
+                        $($second_done)*
+                        $(#[$($attr)*])*
+                        $($rhs_seg $(:: < $($rhs_gen),* >)?)::*
+                        $(( $($rhs_paren)* ))? $({ $($rhs_brace)* })? $([ $($rhs_bracket)* ])?
+                        => $crate::bijection!(@cast_fields $ctx back []
+                            [
+                                $($(($lhs_field $(as $lhs_cast)?))*)?
+                                $($(($lhs_name $(: $lhs_bind)? $(as $lhs_named_cast)?))*)?
+                            ]
+                            $($lhs_seg $(:: < $($lhs_gen),* >)?)::*
+                            $(( $($lhs_field),* ))? $({ $($lhs_name $(: $lhs_bind)?),* })?
+                        ),
+                    }
+                    ($($($rest)*)?)
+                );
+            }
+        );
+    };
+
+    // Whether there were any hints is only known after matching them.
+    // Without any, the branch goes on to the plain arms below as it was, marked so it skips the arm above.
+    (@cast_probe [] ($($branch:tt)*) $hinted:tt) => {
+        $crate::bijection!($($branch)*);
+    };
+    (@cast_probe [$($cast:tt)+] $branch:tt { $($hinted:tt)* }) => {
+        $($hinted)*
+    };
+
+    // The hinted bindings are collected first, the other fields are skipped
+    (@cast_fields $ctx:tt $dir:ident [$($cast:tt)*] [($binding:ident as $ty:ty) $($field:tt)*] $($side:tt)*) => {
+        $crate::bijection!(@cast_fields $ctx $dir [$($cast)* ($binding $ty)] [$($field)*] $($side)*)
+    };
+    (@cast_fields $ctx:tt $dir:ident [$($cast:tt)*] [($name:ident : $binding:ident as $ty:ty) $($field:tt)*] $($side:tt)*) => {
+        $crate::bijection!(@cast_fields $ctx $dir [$($cast)* ($binding $ty)] [$($field)*] $($side)*)
+    };
+    (@cast_fields $ctx:tt $dir:ident [$($cast:tt)*] [$plain:tt $($field:tt)*] $($side:tt)*) => {
+        $crate::bijection!(@cast_fields $ctx $dir [$($cast)*] [$($field)*] $($side)*)
+    };
+    (@cast_fields $ctx:tt $dir:ident [] [] $($side:tt)*) => {
+        $crate::bijection!(@as_expr $ctx $($side)*)
+    };
+    (@cast_fields $ctx:tt into [$(($binding:ident $ty:ty))+] [] $($side:tt)*) => {{
+        $(let $binding = $binding as $ty;)+
+        $crate::bijection!(@as_expr $ctx $($side)*)
+    }};
+    (@cast_fields ([$($flag:tt)*] $($ctx:tt)*) back [$(($binding:ident $ty:ty))+] [] $($side:tt)*) => {
+        $crate::bijection!(@cast_back [$($flag)*] [$($binding)+] ([$($flag)*] $($ctx)*) $($side)*)
+    };
+
+    // With `#[auto_into]`, the binding would only be passed to `Into::into` after the cast back,
+    // which leaves nothing to infer the target of `as _` from
+    (@cast_back [#[auto_into] $($flag:tt)*] $bindings:tt $ctx:tt $($side:tt)*) => {{
+        compile_error!("Field casts (`x as T`) can't be combined with #[auto_into], as the reverse cast couldn't be inferred");
+        ::core::unreachable!()
+    }};
+    (@cast_back [#[$($other:tt)*] $($flag:tt)*] $bindings:tt $ctx:tt $($side:tt)*) => {
+        $crate::bijection!(@cast_back [$($flag)*] $bindings $ctx $($side)*)
+    };
+    (@cast_back [] [$($binding:ident)+] $ctx:tt $($side:tt)*) => {{
+        $(let $binding = $binding as _;)+
+        $crate::bijection!(@as_expr $ctx $($side)*)
+    }};
+
+    // Explicit reverses ending with a block (e.g. ...; reverse Bar::X(n) => { ... } Foo::B => Bar::Y)
+    // These are added right away, and any separator after the block is left over for the arms above
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $first_pat:pat_param $(if $guard:expr)? => $first_expr:expr ;
+            reverse $second_pat:pat => { $($block:tt)* }
+            $($rest:tt)*
+        )
+        $raw:tt
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            {
+                $($first_done)*
+                $(#[$($attr)*])*
+                $first_pat $(if $guard)? => $first_expr,
+            }
+            {
+                $($second_done)*
+                $(#[$($attr)*])*
+                $second_pat => { $($block)* },
+            }
+            ($($rest)*)
+        );
+    };
+
+    // Explicit reverses followed by `;`
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $first_pat:pat_param $(if $guard:expr)? => $first_expr:expr ;
+            reverse $second_pat:pat => $second_expr:expr ;
+            $($rest:tt)*
+        )
+        $raw:tt
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* }
+            { $($second_done)* }
+            (
+                $(#[$($attr)*])*
+                $first_pat $(if $guard)? => $first_expr ;
+                reverse $second_pat => $second_expr,
+                $($rest)*
+            )
+        );
+    };
+
+    // Branches with an explicit reverse (e.g. Foo::A => { ... }; reverse Bar::X(_) => Foo::A)
+    // Neither side is reinterpreted, so both parts are read as `match` branches
+    // The forward part may have a guard (e.g. Foo::Val(n) if n > 0 => ...), which the reverse part doesn't need
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $first_pat:pat_param $(if $guard:expr)? => $first_expr:expr ;
+            reverse $second_pat:pat => $second_expr:expr
+            $(, $($rest:tt)*)?
+        )
+        $raw:tt
+    ) => {
+        $crate::bijection!(@
+            $ctx
+            {
+                $($first_done)*
+                $(#[$($attr)*])*
+                $first_pat $(if $guard)? => $first_expr,
+            }
+            {
+                $($second_done)*
+                $(#[$($attr)*])*
+                $second_pat => $second_expr,
+            }
+            ($($($rest)*)?)
+        );
+    };
+
+    // Wildcard branches without an explicit reverse (e.g. _ => Bar::Other)
+    // `_` is not an expression, so these can only go one way
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* _ => $first_expr:expr $(, $($rest:tt)*)?)
+        $raw:tt
+    ) => {
+        compile_error!(
+            concat!(
+                "Wildcard branches need an explicit reverse (e.g. `_ => ",
+                stringify!($first_expr),
+                "; reverse ",
+                stringify!($first_expr),
+                " => ...`)"
+            )
+        );
+    };
+
+    // Guarded branches without an explicit reverse (e.g. Foo::Val(n) if n > 0 => Bar::Positive(n))
+    // The guard would be lost in reverse, so that direction must be written out
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* $first_pat:pat_param if $guard:expr => $first_expr:expr $(, $($rest:tt)*)?)
+        $raw:tt
+    ) => {
+        compile_error!(
+            concat!(
+                "Guarded branches need an explicit reverse (e.g. `",
+                stringify!($first_pat if $guard => $first_expr),
+                "; reverse ",
+                stringify!($first_expr),
+                " => ...`)"
+            )
+        );
+    };
+
+    // Wildcards and guards followed by `;`, so these still reach their errors
+    (@ $ctx:tt { $($first_done:tt)* } { $($second_done:tt)* } ($(#[$($attr:tt)*])* _ => $first_expr:expr ; $($rest:tt)*) $raw:tt) => {
+        $crate::bijection!(@ $ctx { $($first_done)* } { $($second_done)* } ($(#[$($attr)*])* _ => $first_expr, $($rest)*));
+    };
+    (@ $ctx:tt { $($first_done:tt)* } { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* $first_pat:pat_param if $guard:expr => $first_expr:expr ; $($rest:tt)*)
+        $raw:tt
+    ) => {
+        $crate::bijection!(@ $ctx { $($first_done)* } { $($second_done)* } ($(#[$($attr)*])* $first_pat if $guard => $first_expr, $($rest)*));
+    };
+
+    // Single plain branches, followed by other branches which aren't a run (e.g. Foo::A => Bar::X, 0 => Bar::Y)
+    // These are marked like the runs above. A branch without any cast hints comes back from `@cast_probe` marked as such.
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $(
+                $lhs_first:ident $(:: < $lhs_first_gen:tt $(, $lhs_first_gens:tt)* >)?
+                $(:: $lhs_seg:ident $(:: < $lhs_gen:tt $(, $lhs_gens:tt)* >)?)*
+            )?
+            $(( $($lhs_paren:tt)* ))? $({ $($lhs_brace:tt)* })? $([ $($lhs_bracket:tt)* ])?
+            =>
+            $rhs_first:tt $($rhs_lit:literal)? $(:: < $rhs_first_gen:tt $(, $rhs_first_gens:tt)* >)?
+            $(:: $rhs_seg:ident $(:: < $rhs_gen:tt $(, $rhs_gens:tt)* >)?)*
+            $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            , $($rest:tt)*
+        )
+        [$($raw:tt)*]
+        $(unhinted)?
+    ) => {
+        $crate::bijection!(@zip $ctx { $($first_done)* } { $($second_done)* } []
+            [
+                $((a #) (a [$($attr)*]))*
+                $(
+                    (l $lhs_first) $((l ::) (l <) (l $lhs_first_gen) $((l ,) (l $lhs_first_gens))* (l >))?
+                    $((l ::) (l $lhs_seg) $((l ::) (l <) (l $lhs_gen) $((l ,) (l $lhs_gens))* (l >))?)*
+                )?
+                $((l ( $($lhs_paren)* )))? $((l { $($lhs_brace)* }))? $((l [ $($lhs_bracket)* ]))?
+                (e =>)
+                (r $rhs_first) $((r $rhs_lit))? $((r ::) (r <) (r $rhs_first_gen) $((r ,) (r $rhs_first_gens))* (r >))?
+                $((r ::) (r $rhs_seg) $((r ::) (r <) (r $rhs_gen) $((r ,) (r $rhs_gens))* (r >))?)*
+                $((r ( $($rhs_paren)* )))? $((r { $($rhs_brace)* }))? $((r [ $($rhs_bracket)* ]))?
+                , $((x $rest))*
+            ]
+            [$($raw)*]
+        );
+    };
+
+    // Literals on the left side (e.g. 0 => Bar::X)
+    // These can't be told apart from paths by a single matcher, as `true` and `false` are both.
+    // The literal is read from the copy right away, as it may be two tokens (e.g. `-1`).
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $lhs_lit:literal
+            =>
+            $rhs_first:tt $($rhs_lit:literal)? $(:: < $rhs_first_gen:tt $(, $rhs_first_gens:tt)* >)?
+            $(:: $rhs_seg:ident $(:: < $rhs_gen:tt $(, $rhs_gens:tt)* >)?)*
+            $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            $(, $($rest:tt)*)?
+        )
+        [$(#[$($raw_attr:tt)*])* $first_lit:literal => $($raw:tt)*]
+    ) => {
+        $crate::bijection!(@zip $ctx { $($first_done)* } { $($second_done)* }
+            [$(((a #) #) ((a [$($raw_attr)*]) [$($raw_attr)*]))* ((l $lhs_lit) $first_lit) ((e =>) =>)]
+            [
+                (r $rhs_first) $((r $rhs_lit))? $((r ::) (r <) (r $rhs_first_gen) $((r ,) (r $rhs_first_gens))* (r >))?
+                $((r ::) (r $rhs_seg) $((r ::) (r <) (r $rhs_gen) $((r ,) (r $rhs_gens))* (r >))?)*
+                $((r ( $($rhs_paren)* )))? $((r { $($rhs_brace)* }))? $((r [ $($rhs_bracket)* ]))?
+                $(, $((x $rest))*)?
+            ]
+            [$($raw)*]
+        );
+    };
+    (@
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        (
+            $(#[$($attr:tt)*])*
+            $lhs_lit:literal
+            =>
+            $rhs_first:tt $($rhs_lit:literal)? $(:: < $rhs_first_gen:tt $(, $rhs_first_gens:tt)* >)?
+            $(:: $rhs_seg:ident $(:: < $rhs_gen:tt $(, $rhs_gens:tt)* >)?)*
+            $(( $($rhs_paren:tt)* ))? $({ $($rhs_brace:tt)* })? $([ $($rhs_bracket:tt)* ])?
+            ; $($rest:tt)*
+        )
+        [$(#[$($raw_attr:tt)*])* $first_lit:literal => $($raw:tt)*]
+    ) => {
+        $crate::bijection!(@zip $ctx { $($first_done)* } { $($second_done)* }
+            [$(((a #) #) ((a [$($raw_attr)*]) [$($raw_attr)*]))* ((l $lhs_lit) $first_lit) ((e =>) =>)]
+            [
+                (r $rhs_first) $((r $rhs_lit))? $((r ::) (r <) (r $rhs_first_gen) $((r ,) (r $rhs_first_gens))* (r >))?
+                $((r ::) (r $rhs_seg) $((r ::) (r <) (r $rhs_gen) $((r ,) (r $rhs_gens))* (r >))?)*
+                $((r ( $($rhs_paren)* )))? $((r { $($rhs_brace)* }))? $((r [ $($rhs_bracket)* ]))?
+                ; $((x $rest))*
+            ]
+            [$($raw)*]
+        );
+    };
+
+    // ===== Zipped branches =====
+    // Each mark is paired with its copied token: ((part mark) token)
+    // The parts are `a` for attributes, `l` and `r` for the sides, `e` for the arrow, and `x` for the rest of the block.
+    // Separators are marked by themselves: (, ,) or (; ;)
+    // Tokens read from the copy already (e.g. a literal) come paired up in the first list.
+    // The copied tokens then go into the `match` arms as they are.
+    (@zip $ctx:tt $first_done:tt $second_done:tt [$($zipped:tt)*] [$($mark:tt)*] [$($token:tt)*]) => {
+        $crate::bijection!(@zipped $ctx $first_done $second_done () $($zipped)* $(($mark $token))*);
+    };
+
+    // The last branch has an explicit reverse (e.g. Foo::A(x) => Bar::X(x); reverse Bar::X(x) => Foo::A(x)),
+    // so it's put back in front of its reverse for the arms reading one (see `@zipped_last`)
+    (@zipped
+    $ctx:tt
+        $first_done:tt
+        $second_done:tt
+        ()
+        $(
+            $(((a $attr_mark:tt) $attr:tt))*
+            $(((l $lhs_mark:tt) $lhs:tt))+
+            ((e $arrow_mark:tt) $arrow:tt)
+            $(((r $rhs_mark:tt) $rhs:tt))+
+            $((, $comma:tt))? $((; $semi:tt))?
+        )+
+        ((x $reverse_mark:tt) reverse)
+        $(((x $rest_mark:tt) $rest:tt))*
+    ) => {
+        $crate::bijection!(@zipped_last $ctx $first_done $second_done
+            [$((
+                $($comma)? $($semi)?
+                { $(((a $attr_mark) $attr))* $(((l $lhs_mark) $lhs))+ ((e $arrow_mark) $arrow) $(((r $rhs_mark) $rhs))+ }
+            ))+]
+            [reverse $($rest)*]
+        );
+    };
+
+    // The branches are added, and the rest goes through the arms again.
+    // With a slot of `{}` instead of a copy, only the arms reading an explicit reverse apply.
+    (@zipped
+    $ctx:tt
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($($next:tt)*)
+        $(
+            $(((a $attr_mark:tt) $attr:tt))*
+            $(((l $lhs_mark:tt) $lhs:tt))+
+            ((e $arrow_mark:tt) $arrow:tt)
+            $(((r $rhs_mark:tt) $rhs:tt))+
+            $((, $comma:tt))? $((; $semi:tt))?
+        )*
+        $(((x $rest_mark:tt) $rest:tt))*
+    ) => {
+        $(
+            $crate::bijection!(@reject_inner_or_in ($($lhs)* => $($rhs)*) $($lhs)* $($rhs)*);
+        )*
+        $crate::bijection!(@
+            $ctx
+            { $($first_done)* $($($attr)* $($lhs)* => $crate::bijection!(@as_expr $ctx $($rhs)*),)* }
+            { $($second_done)* $($($attr)* $($rhs)* => $crate::bijection!(@as_expr $ctx $($lhs)*),)* }
+            ($($rest)*)
+            $($next)*
+        );
+    };
+    (@zipped_last
+    $ctx:tt
+        $first_done:tt
+        $second_done:tt
+        [
+            $((, { $($branch:tt)* }))*
+            (; { $(((a $attr_mark:tt) $attr:tt))* $(((l $lhs_mark:tt) $lhs:tt))+ ((e $arrow_mark:tt) $arrow:tt) $(((r $rhs_mark:tt) $rhs:tt))+ })
+        ]
+        [$($rest:tt)*]
+    ) => {
+        $crate::bijection!(@zipped $ctx $first_done $second_done ({})
+            $($($branch)* (, ,))*
+            $(((x a) $attr))* $(((x l) $lhs))+ ((x e) $arrow) $(((x r) $rhs))+ ((x ;) ;) $(((x r) $rest))*
+        );
+    };
+
+    // ===== Sides read as expressions =====
+
+    // Reinterpreting a pattern as an expression mostly works as-is, except for `@`-bindings
+    // (e.g. Foo::Num(n @ 1..=9) => Bar::Digit(n)), where only the binding is kept: Foo::Num(n),
+    // and `ref` bindings, which are bound by reference and cloned: Foo::Name(ref s) becomes Foo::Name(Clone::clone(s)).
+    // The side is rewritten where it is used, so the `match` arms stay as written otherwise.
+    // Plain paths have nothing to rewrite, which skips the munching for unit variants.
+    // With `#[auto_into]`, bindings are also converted with `Into::into` (see the `into` arms below).
+    (@as_expr $ctx:tt $($seg:ident $(:: < $($gen:ty),* >)?)::*) => {
+        $($seg $(:: < $($gen),* >)?)::*
+    };
+    (@as_expr ([$($flag:tt)*] $($ctx:tt)*) $($side:tt)*) => {
+        $crate::bijection!(@as_expr_mode [$($flag)*] $($side)*)
+    };
+    (@as_expr_mode [#[auto_into] $($flag:tt)*] $($side:tt)*) => {
+        $crate::bijection!(@strip_bindings into [] {} $($side)*)
+    };
+    (@as_expr_mode [#[$($other:tt)*] $($flag:tt)*] $($side:tt)*) => {
+        $crate::bijection!(@as_expr_mode [$($flag)*] $($side)*)
+    };
+    (@as_expr_mode [] $($side:tt)*) => {
+        $crate::bijection!(@strip_bindings plain [] {} $($side)*)
+    };
+
+    // The side is munched token by token, descending into groups.
+    // The stack keeps the enclosing output and remaining tokens for each group.
+    // Rest patterns (e.g. Foo { x, .. }) drop fields, which can't be made up again
+    // Subpatterns are skipped before reaching this (e.g. [first, rest @ ..])
+    (@strip_bindings $mode:ident $stack:tt $out:tt $(,)? .. $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@rest_pattern_error)
+    };
+    (@strip_bindings $mode:ident $stack:tt { $($out:tt)* } $binding:ident @ $($rest:tt)*) => {
+        $crate::bijection!(@skip_subpattern $mode $stack { $($out)* $binding } $($rest)*)
+    };
+
+    // `ref mut` is treated like `ref`, the value is cloned either way
+    (@strip_bindings $mode:ident $stack:tt $out:tt ref mut $binding:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack $out ref $binding $($rest)*)
+    };
+    (@strip_bindings $mode:ident $stack:tt $out:tt : ref mut $binding:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack $out : ref $binding $($rest)*)
+    };
+    (@strip_bindings $mode:ident $stack:tt { $($out:tt)* } ref $binding:ident @ $($rest:tt)*) => {
+        $crate::bijection!(@skip_subpattern $mode $stack { $($out)* ::core::clone::Clone::clone($binding) } $($rest)*)
+    };
+    // Ex: Foo { key: ref k }
+    (@strip_bindings $mode:ident $stack:tt { $($out:tt)* } : ref $binding:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack { $($out)* : ::core::clone::Clone::clone($binding) } $($rest)*)
+    };
+    // Ex: Foo { ref key }, a field shorthand needs the field name again
+    (@strip_bindings $mode:ident [(brace $($group:tt)*) $($stack:tt)*] { $($out:tt)* } ref $binding:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode [(brace $($group)*) $($stack)*] { $($out)* $binding: ::core::clone::Clone::clone($binding) } $($rest)*)
+    };
+    (@strip_bindings $mode:ident $stack:tt { $($out:tt)* } ref $binding:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack { $($out)* ::core::clone::Clone::clone($binding) } $($rest)*)
+    };
+
+    // Bindings are converted with `Into::into`.
+    // A binding is an identifier ending an element (followed by `,` or the end of its group),
+    // which isn't a path segment or a field name, so `Some(x)`, `Foo::A` and `Foo { x: y }` only convert `x` and `y`.
+    (@strip_bindings into $stack:tt { $($out:tt)* } $binding:ident @ $($rest:tt)*) => {
+        $crate::bijection!(@skip_subpattern into $stack { $($out)* ::core::convert::Into::into($binding) } $($rest)*)
+    };
+    (@strip_bindings into $stack:tt { $($out:tt)* } $seg:ident :: $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings into $stack { $($out)* $seg } :: $($rest)*)
+    };
+    (@strip_bindings into $stack:tt { $($out:tt)* } :: $seg:ident $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings into $stack { $($out)* :: $seg } $($rest)*)
+    };
+    // Ex: Foo { key: k }, the colon is left for the arms reading the field's value (including `: ref k`)
+    (@strip_bindings into $stack:tt { $($out:tt)* } $field:ident : $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings into $stack { $($out)* $field } : $($rest)*)
+    };
+    (@strip_bindings into $stack:tt { $($out:tt)* } : $binding:ident $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@strip_bindings into $stack { $($out)* : ::core::convert::Into::into($binding) } $(, $($rest)*)?)
+    };
+    // Ex: Foo { x }, a field shorthand needs the field name again
+    (@strip_bindings into [(brace $($group:tt)*) $($stack:tt)*] { $($out:tt)* } $binding:ident $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@strip_bindings into [(brace $($group)*) $($stack)*]
+            { $($out)* $binding: ::core::convert::Into::into($binding) } $(, $($rest)*)?
+        )
+    };
+    (@strip_bindings into $stack:tt { $($out:tt)* } $binding:ident $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@strip_bindings into $stack { $($out)* ::core::convert::Into::into($binding) } $(, $($rest)*)?)
+    };
+
+    (@strip_bindings $mode:ident [$($stack:tt)*] $out:tt ( $($inner:tt)* ) $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode [(paren $out ($($rest)*)) $($stack)*] {} $($inner)*)
+    };
+    (@strip_bindings $mode:ident [$($stack:tt)*] $out:tt [ $($inner:tt)* ] $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode [(bracket $out ($($rest)*)) $($stack)*] {} $($inner)*)
+    };
+    (@strip_bindings $mode:ident [$($stack:tt)*] $out:tt { $($inner:tt)* } $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode [(brace $out ($($rest)*)) $($stack)*] {} $($inner)*)
+    };
+    (@strip_bindings $mode:ident $stack:tt { $($out:tt)* } $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack { $($out)* $token } $($rest)*)
+    };
+
+    // End of a group
+    (@strip_bindings $mode:ident [(paren { $($outer:tt)* } ($($rest:tt)*)) $($stack:tt)*] { $($out:tt)* }) => {
+        $crate::bijection!(@strip_bindings $mode [$($stack)*] { $($outer)* ( $($out)* ) } $($rest)*)
+    };
+    (@strip_bindings $mode:ident [(bracket { $($outer:tt)* } ($($rest:tt)*)) $($stack:tt)*] { $($out:tt)* }) => {
+        $crate::bijection!(@strip_bindings $mode [$($stack)*] { $($outer)* [ $($out)* ] } $($rest)*)
+    };
+    (@strip_bindings $mode:ident [(brace { $($outer:tt)* } ($($rest:tt)*)) $($stack:tt)*] { $($out:tt)* }) => {
+        $crate::bijection!(@strip_bindings $mode [$($stack)*] { $($outer)* { $($out)* } } $($rest)*)
+    };
+    (@strip_bindings $mode:ident [] { $($out:tt)* }) => {
+        $($out)*
+    };
+
+    // The subpattern ends at the next comma (or the end of its group)
+    (@skip_subpattern $mode:ident $stack:tt { $($out:tt)* } , $($rest:tt)*) => {
+        $crate::bijection!(@strip_bindings $mode $stack { $($out)* , } $($rest)*)
+    };
+    (@skip_subpattern $mode:ident $stack:tt $out:tt $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@skip_subpattern $mode $stack $out $($rest)*)
+    };
+    (@skip_subpattern $mode:ident $stack:tt $out:tt) => {
+        $crate::bijection!(@strip_bindings $mode $stack $out)
+    };
+
+    // ===== Or-pattern checks =====
+
+    // Outer or-patterns (e.g. Foo(0) | Foo(1) => Bar(0))
+    // The valid munching arms reject these already, as the pattern is followed by `|` instead of `=>`
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty $(, $($mode:tt)*)?)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($(#[$($attr:tt)*])* $first_pat:pat_param $(| $alt_pat:pat_param)+ => $first_expr:expr $(, $($rest:tt)*)?)
+        $raw:tt
+    ) => {
+        compile_error!(
+            concat!(
+                "Or-patterns are not supported in bijection branches:\n",
+                stringify!($first_pat $(| $alt_pat)+ => $first_expr)
+            )
+        );
+    };
+
+    // Inner or-patterns (e.g. Foo(2 | 3) => Bar(1))
+    // These pass as valid patterns *and* expressions (bitwise or!), so the groups of each side
+    // are scanned for `|` tokens separately.
+    // Each token is checked by its own invocation, which keeps the recursion depth
+    // proportional to the nesting depth, rather than the length of the branch.
+    // The whole branch is kept around for the error message.
+    (@reject_inner_or_in $branch:tt $($token:tt)*) => {
+        $( $crate::bijection!(@reject_inner_or_token $branch $token); )*
+    };
+
+    (@reject_inner_or_token $branch:tt |) => {
+        $crate::bijection!(@reject_inner_or_error $branch);
+    };
+    (@reject_inner_or_token $branch:tt ( $($inner:tt)* )) => {
+        $crate::bijection!(@reject_inner_or_in $branch $($inner)*);
+    };
+    (@reject_inner_or_token $branch:tt [ $($inner:tt)* ]) => {
+        $crate::bijection!(@reject_inner_or_in $branch $($inner)*);
+    };
+    (@reject_inner_or_token $branch:tt { $($inner:tt)* }) => {
+        $crate::bijection!(@reject_inner_or_in $branch $($inner)*);
+    };
+    (@reject_inner_or_token $branch:tt $token:tt) => {};
+
+    (@reject_inner_or_error ($($branch:tt)*)) => {
+        compile_error!(
+            concat!(
+                "Or-patterns are not supported in bijection branches:\n",
+                stringify!($($branch)*)
+            )
+        );
+    };
+
+    (@rest_pattern_error) => {
+        compile_error!(concat!(
+            "Rest patterns (`..`) can't be read as an expression, as the skipped fields are unknown; ",
+            "give the branch an explicit reverse instead (`<pattern> => <expression>; reverse <pattern> => <expression>`)"
+        ))
+    };
+
+    // ===== Duplicate checks =====
+
+    // Duplicate right-hand sides (e.g. Foo(0) => Bar(0), Foo(1) => Bar(0))
+    // These are the patterns of the reverse `match`, so a duplicate would never be reached.
+    // Tokens can't be compared by macro_rules itself, so the stringified patterns are compared
+    // during const evaluation instead. Only textual duplicates are found this way.
+    // Branches disabled by `#[cfg(...)]` are skipped, as they may well share a right-hand side with another one.
+    // The forward arms are only used to show the original branches, as their expressions may have been rewritten.
+    (@reject_duplicates
+        { $($(#[$($first_attr:tt)*])* $first_pat:pat $(if $first_guard:expr)? => $first_expr:expr,)* }
+        { $($(#[$($attr:tt)*])* $pat:pat => $expr:expr,)* }
+    ) => {
+        const _: () = {
+            const BRANCHES: &[(&str, &str, bool)] = &[$((
+                stringify!($pat),
+                stringify!($first_pat => $pat),
+                true $(&& $crate::bijection!(@cfg_enabled $($attr)*))*
+            )),*];
+            $crate::bijection!(@panic_on_duplicate $crate::__private::DUPLICATE_RHS, BRANCHES);
+        };
+    };
+
+    // Duplicate left-hand sides with `#[strict]` (e.g. Foo(0) => Bar(0), Foo(0) => Bar(1))
+    // These are unreachable patterns of the forward `match`, which the denied lint reports as well.
+    // This check comes first, and names both branches.
+    // The branches are collected first, as they may still be written as-is (e.g. forward-only blocks without a comma).
+    (@reject_duplicate_patterns [$($branch:tt)*]) => {
+        const _: () = {
+            const BRANCHES: &[(&str, &str, bool)] = &[$($branch),*];
+            $crate::bijection!(@panic_on_duplicate $crate::__private::DUPLICATE_LHS, BRANCHES);
+        };
+    };
+    (@reject_duplicate_patterns [$($branch:tt)*]
+        $(#[$($attr:tt)*])* $pat:pat $(if $guard:expr)? => $($rest:tt)*
+    ) => {
+        $crate::bijection!(@reject_duplicate_patterns_skip [$($branch)* (
+            stringify!($pat $(if $guard)?),
+            stringify!($pat $(if $guard)?),
+            true $(&& $crate::bijection!(@cfg_enabled $($attr)*))*
+        )] $($rest)*);
+    };
+    (@reject_duplicate_patterns_skip $branches:tt { $($block:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@reject_duplicate_patterns $branches $($($rest)*)?);
+    };
+    (@reject_duplicate_patterns_skip $branches:tt $(, $($rest:tt)*)?) => {
+        $crate::bijection!(@reject_duplicate_patterns $branches $($($rest)*)?);
+    };
+    (@reject_duplicate_patterns_skip $branches:tt $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@reject_duplicate_patterns_skip $branches $($rest)*);
+    };
+
+    (@panic_on_duplicate $header:expr, $branches:ident) => {
+        const DUPLICATE: ::core::option::Option<(usize, usize)> =
+            $crate::__private::find_duplicate($branches);
+        const MESSAGE_LEN: usize = $crate::__private::duplicate_message_len($header, $branches, DUPLICATE);
+        const MESSAGE: [u8; MESSAGE_LEN] = $crate::__private::duplicate_message($header, $branches, DUPLICATE);
+
+        if DUPLICATE.is_some() {
+            ::core::panic!("{}", $crate::__private::as_str(&MESSAGE));
+        }
+    };
+
+    // Identical types (e.g. bijection!(Foo, Foo, { ... }))
+    // The impls would conflict with each other (or the blanket `From<T> for T`), far from the actual cause.
+    // Like above, this is a textual comparison, so aliases are not caught.
+    (@reject_same_types $first_ty:ty, $second_ty:ty) => {
+        const _: () = {
+            if $crate::__private::str_eq(stringify!($first_ty), stringify!($second_ty)) {
+                ::core::panic!("Cannot create a bijection between a type and itself");
+            }
+        };
+    };
+
+    (@cfg_enabled cfg($($predicate:tt)*)) => { cfg!($($predicate)*) };
+    (@cfg_enabled $($attr:tt)*) => { true };
+
+    // ===== Invalid patterns for better compiler errors =====
+
+    // Notes:
+    // - Using the matched type tokens somehow helps with highlighting (at least in RustRover).
+    //   This is done via loose `let` declarations.
+    // - Similarly for other tokens
+
+    // Internal macro errors
+
+    // Invalid bijection match statements (e.g. Foo::A = Bar::X)
+    // Only the first remaining branch is reported, as the ones before it were valid.
+    (@
+    ([$($flag:tt)*] $generics:tt $first_ty:ty, $second_ty:ty $(, $($mode:tt)*)?)
+        { $($first_done:tt)* }
+        { $($second_done:tt)* }
+        ($($rest:tt)*)
+        $($raw:tt)*
+    ) => {
+        $crate::bijection!(@invalid_branch ($first_ty, $second_ty) [] $($rest)*);
+    };
+
+    // The branch is collected up to the next separator (keeping an explicit reverse)
+    (@invalid_branch $types:tt [$($branch:tt)*] ; reverse $($rest:tt)*) => {
+        $crate::bijection!(@invalid_branch $types [$($branch)* ; reverse] $($rest)*);
+    };
+    (@invalid_branch $types:tt [$($branch:tt)*] , $($rest:tt)*) => {
+        $crate::bijection!(@invalid_branch $types [$($branch)*]);
+    };
+    (@invalid_branch $types:tt [$($branch:tt)*] ; $($rest:tt)*) => {
+        $crate::bijection!(@invalid_branch $types [$($branch)*]);
+    };
+    (@invalid_branch $types:tt [$($branch:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@invalid_branch $types [$($branch)* $token] $($rest)*);
+    };
+    (@invalid_branch $types:tt [$($branch:tt)*]) => {
+        $crate::bijection!(@separator_hint $types [$($branch)*] $($branch)*);
+    };
+
+    // Common mistakes for the `=>` between the sides get a suggestion instead
+    // (`=` is left to the native error of the `match` below, which already suggests `=>`)
+    // Groups are single tokens here, so a `:` inside a struct pattern isn't mistaken for one
+    (@separator_hint $types:tt [$($branch:tt)*] : $($rest:tt)*) => {
+        $crate::bijection!(@separator_error ":" $($branch)*);
+    };
+    (@separator_hint $types:tt [$($branch:tt)*] <-> $($rest:tt)*) => {
+        $crate::bijection!(@separator_error "<->" $($branch)*);
+    };
+    (@separator_hint $types:tt [$($branch:tt)*] -> $($rest:tt)*) => {
+        $crate::bijection!(@separator_error "->" $($branch)*);
+    };
+    (@separator_hint $types:tt [$($branch:tt)*] <=> $($rest:tt)*) => {
+        $crate::bijection!(@bidirectional_error [$($branch)*] $($branch)*);
+    };
+    (@separator_hint $types:tt $branch:tt $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@separator_hint $types $branch $($rest)*);
+    };
+    (@separator_hint $types:tt $branch:tt) => {
+        $crate::bijection!(@invalid_report $types $branch);
+    };
+
+    (@separator_error $separator:literal $($branch:tt)*) => {
+        compile_error!(concat!(
+            "Use `=>`, not `", $separator, "`, to separate bijection sides (got: ",
+            stringify!($($branch)*),
+            ")"
+        ));
+    };
+
+    // A `<=>` after `=>` belongs to an inverse function instead (see "Inverse functions")
+    (@bidirectional_error [$($branch:tt)*] => $($rest:tt)*) => {
+        compile_error!(concat!(
+            "Inverse functions must be written as `<pattern> => <path>(<function>(<binding>)) <=> <inverse function or closure>` (got: ",
+            stringify!($($branch)*),
+            ")"
+        ));
+    };
+    (@bidirectional_error $branch:tt $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@bidirectional_error $branch $($rest)*);
+    };
+    (@bidirectional_error [$($branch:tt)*]) => {
+        compile_error!(concat!(
+            "Both sides of a `<=>` branch must be reversible, i.e. a path (with an optional group) or a literal, ",
+            "without wildcards, alternatives, ranges or `@` bindings (got: ",
+            stringify!($($branch)*),
+            ")"
+        ));
+    };
+
+    (@invalid_report ($first_ty:ty, $second_ty:ty) [$($branch:tt)*]) => {
+        // Wrapped in a fn, so the `match` is only type-checked, never evaluated
+        const _: () = {
+            #[allow(dead_code)]
+            fn invalid_branch() {
+                let _: $first_ty;
+                let _: $second_ty;
+                // This match statement might produce a better (native) compiler error message
+                // Example: `Foo::A = Bar::X` will make it complain about needing `=>` instead
+                // The #allow suppresses an unnecessary lint, and the wildcard an exhaustiveness error
+                #[allow(unreachable_code)]
+                match ::core::unreachable!() {
+                    $($branch)*,
+                    _ => ::core::unreachable!(),
+                };
+            }
+        };
+        compile_error!(concat!("Invalid bijection pattern:\n", stringify!($($branch)*)));
+    };
+
+    // Reverse-only entry (see "Entry (reverse only)" below)
+    (@reverse_entry [$($first:tt)*] <= $second_ty:ty, {$($bij:tt)*} $($tail:tt)*) => {
+        $crate::bijection!(@reverse_types ($($first)*) $second_ty, { $($bij)* } $($tail)*);
+    };
+    (@reverse_entry [$($first:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::bijection!(@reverse_entry [$($first)* $token] $($rest)*);
+    };
+
+    // The mirror of forward-only conversions: the left-hand sides are the expressions, and the right-hand sides
+    // the patterns, so the branches are swapped and go through the forward-only entry with the types swapped as well.
+    // Ex: Foo::N(x + 1) => Bar::N(x), becomes Bar::N(x) => Foo::N(x + 1) for `Bar => Foo`
+    (@reverse_types ($(#[$($flag:tt)*])* $first_ty:ty) $second_ty:ty, {$($bij:tt)*} $($tail:tt)*) => {
+        $crate::bijection!(@reverse_swap ([$(#[$($flag)*])*] $second_ty, $first_ty, [$($tail)*]) [] ($($bij)*));
+    };
+    (@reverse_swap $ctx:tt [$($done:tt)*] (use $($rest:tt)*)) => {
+        $crate::bijection!(@reverse_use $ctx [$($done)* use] ($($rest)*));
+    };
+    (@reverse_swap $ctx:tt [$($done:tt)*]
+        ($(#[$($attr:tt)*])* $expr:expr => $pat:pat $(if $guard:expr)? $(, $($rest:tt)*)?)
+    ) => {
+        $crate::bijection!(@reverse_swap $ctx [$($done)* $(#[$($attr)*])* $pat $(if $guard)? => $expr,] ($($($rest)*)?));
+    };
+    (@reverse_swap ($flags:tt $first_ty:ty, $second_ty:ty, [$($tail:tt)*]) [$($done:tt)*] ()) => {
+        $crate::bijection!(@entry $flags ([] []) $first_ty => $second_ty, { $($done)* } $($tail)*);
+    };
+    // Like forward-only branches, these are separated by commas, and have no explicit reverse
+    (@reverse_swap $ctx:tt $done:tt ($($branch:tt)+)) => {
+        compile_error!(concat!(
+            "Invalid reverse-only branch, expected `<expression> => <pattern>` (without an explicit reverse):\n",
+            stringify!($($branch)+)
+        ));
+    };
+    // Leading `use` declarations are kept as they are, for the forward-only entry (see "Imports")
+    (@reverse_use $ctx:tt [$($done:tt)*] (; $($rest:tt)*)) => {
+        $crate::bijection!(@reverse_swap $ctx [$($done)* ;] ($($rest)*));
+    };
+    (@reverse_use $ctx:tt [$($done:tt)*] ($token:tt $($rest:tt)*)) => {
+        $crate::bijection!(@reverse_use $ctx [$($done)* $token] ($($rest)*));
+    };
+    (@reverse_use $ctx:tt [$($done:tt)*] ()) => {
+        $crate::bijection!(@reverse_swap $ctx [$($done)*] ());
+    };
+
+    // Without a `<=` anywhere, this is the fallback for the entries (see "Fallback, catches everything else")
+    (@reverse_entry [$($unknown:tt)*]) => {
+        compile_error!("Expected: TypeA, TypeB, { /* bijection patterns */ }");
+    };
+    (@reverse_types $($unknown:tt)*) => {
+        compile_error!("Expected: TypeA <= TypeB, { /* bijection patterns */ }");
+    };
+
+    // Fallback
+    (@ $($unknown:tt)*) => {
+        const _: () = {
+            // Uncomment stringify and comment out the compiler error for debugging
+            // const _: &str = concat!($(stringify!($unknown)),*);
+            compile_error!("Uncaught internal macro error");
+        };
+    };
+
+    // Incorrect syntax
+
+    // Ex: bijection!(Foo, Bar)
+    ($first_ty:ty, $second_ty:ty $(,)?) => {
+        const _: () = {
+            let _: $first_ty;
+            let _: $second_ty;
+            compile_error!("Missing bijection declaration block after types");
+        };
+    };
+
+    // Ex: bijection!(Foo, Bar {})
+    ($first_ty:ty, $second_ty:tt { $($bij:tt)* }) => {
+        const _: () = {
+            let _: $first_ty;
+            let _: $second_ty;
+            compile_error!("Bijection declaration block must be separated with a comma");
+        };
+    };
+
+    // Ex: bijection!(Foo, Bar, include!("foo_bar.in"))
+    // The macro can't expand `include!` eagerly, so the branches would only be seen as its tokens
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty, include! ($($path:tt)*)) => {
+        compile_error!(concat!(
+            "The declaration block can't be included, include the whole invocation instead: include!(",
+            stringify!($($path)*),
+            ")"
+        ));
+    };
+
+    // Ex: bijection!(Foo, Bar, Foo::A => Bar::X)
+    ($first_ty:ty, $second_ty:tt, $($bij:tt)+) => {
+        const _: () = {
+            let _: $first_ty;
+            let _: $second_ty;
+            compile_error!(
+                concat!(
+                    "Bijection declaration block expected (got: ",
+                    stringify!($($bij)+),
+                    ")"
+                )
+            );
+        };
+    };
+
+    // Same as the above without the comma
+    // Ex: bijection!(Foo, Bar Foo::A => Bar::X)
+    ($first_ty:ty, $second_ty:tt $($bij:tt)+) => {
+        const _: () = {
+            let _: $first_ty;
+            let _: $second_ty;
+            compile_error!(
+                concat!(
+                    "Bijection declaration block expected (got: ",
+                    stringify!($($bij)+),
+                    ")"
+                )
+            );
+        };
+    };
+
+    // Ex: bijection!(Foo, { Foo::A => Bar::X })
+    ($first_ty:ty $(, $($bij:tt)*)?) => {
+        const _: () = {
+            let _: $first_ty;
+            compile_error!("Missing second type");
+        };
+    };
+
+    // Ex: bijection!(Foo { Foo::A => Bar::X })
+    ($first_ty:ty { $($bij:tt)* }) => {
+        const _: () = {
+            let _: $first_ty;
+            compile_error!("Bijection declaration block must be separated with a comma");
+        };
+    };
+
+    // Ex: bijection!({ Foo::A => Bar::X })
+    // Catches anything contained in curly braces without the leading types
+    ({$($bij:tt)*}) => {
+        compile_error!("Missing types before declaration block");
+    };
+
+    // Entry (reverse only), and the fallback catching everything else
+    // Ex: bijection!(Foo <= Bar, { ... })
+    // A type can't be followed by `<=`, so anything else is searched for it token by token
+    ($($tokens:tt)*) => {
+        $crate::bijection!(@reverse_entry [] $($tokens)*);
+    };
+
+}
+
+/// Generates `TryFrom` impls for any two types, for mappings that are only partial in either direction.
+///
+/// The syntax is the same as with [`bijection!`], with an additional error type after the two types.
+///
+/// # Usage
+/// ```text
+/// try_bijection!(Foo, Bar, Error, {
+///     Foo::A => Bar::X,
+///     Foo::B(b) => Bar::Y(b),
+///     // ...
+/// });
+/// ```
+/// Both generated `match` statements are built the same way as with [`bijection!`] - the forward
+/// one uses the branches as written, and the reverse one swaps the sides of each branch.
+/// Matched values are returned as `Ok`, and each `match` then ends with a catch-all that returns
+/// `Err(Error::from(value))` for anything the branches did not cover.
+/// The error type must therefore implement `From` for both types.
+/// It may also be given by name, as in `try_bijection!(Foo, Bar, error = Error, { ... })`.
+///
+/// If a direction is exhaustive already, its catch-all is simply never reached.
+///
+/// Flags go before the types, like with [`bijection!`] (e.g. `try_bijection!(#[by_ref] Foo, Bar, { ... })`).
+///
+/// The error type may also be left out, as in `try_bijection!(Foo, Bar, { ... })`.
+/// Each direction then returns a [`BijectionError`] holding the unmapped value,
+/// i.e. `BijectionError<Foo>` when converting from `Foo`, and `BijectionError<Bar>` the other way around.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::try_bijection;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+///     Z,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Error {
+///     UnmappedFoo(Foo),
+///     UnmappedBar(Bar),
+/// }
+///
+/// impl From<Foo> for Error {
+///     fn from(value: Foo) -> Self {
+///         Error::UnmappedFoo(value)
+///     }
+/// }
+///
+/// impl From<Bar> for Error {
+///     fn from(value: Bar) -> Self {
+///         Error::UnmappedBar(value)
+///     }
+/// }
+///
+/// try_bijection!(Foo, Bar, Error, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// assert_eq!(Bar::try_from(Foo::A), Ok(Bar::X));
+/// assert_eq!(Bar::try_from(Foo::C), Err(Error::UnmappedFoo(Foo::C)));
+/// assert_eq!(Foo::try_from(Bar::Y), Ok(Foo::B));
+/// assert_eq!(Foo::try_from(Bar::Z), Err(Error::UnmappedBar(Bar::Z)));
+/// ```
+///
+/// With the default error type:
+/// ```rust
+/// use biject_into::{try_bijection, BijectionError};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// try_bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+/// });
+///
+/// assert_eq!(Bar::try_from(Foo::A), Ok(Bar::X));
+/// let error = Foo::try_from(Bar::Y).unwrap_err();
+/// assert_eq!(error, BijectionError { value: Bar::Y });
+/// assert_eq!(error.to_string(), "no bijection branch for Y");
+/// ```
+#[macro_export]
+macro_rules! try_bijection {
+    // Entry (with the default error type)
+    // Ex: try_bijection!(Foo, Bar, { ... })
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty,
+        {$($bij:tt)*}
+    ) => {
+        $crate::bijection!(@
+            ([$(#[$($flag)*])*] ([] []) $first_ty, $second_ty, try $crate::BijectionError<$first_ty>, $crate::BijectionError<$second_ty>)
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Entry (with a named error type)
+    // Ex: try_bijection!(Foo, Bar, error = Error, { ... })
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty, error = $error_ty:ty,
+        {$($bij:tt)*}
+    ) => {
+        $crate::try_bijection!($(#[$($flag)*])* $first_ty, $second_ty, $error_ty, { $($bij)* });
+    };
+
+    // Entry
+    ($(#[$($flag:tt)*])* $first_ty:ty, $second_ty:ty, $error_ty:ty,
+        {$($bij:tt)*}
+    ) => {
+        $crate::bijection!(@
+            ([$(#[$($flag)*])*] ([] []) $first_ty, $second_ty, try $error_ty, $error_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: TypeA, TypeB, [ErrorType,] { /* bijection patterns */ }");
+    };
+}
+
+/// Generates inherent conversion methods for any two types, instead of `From` impls.
+///
+/// This is useful when the orphan rule gets in the way, e.g. when one of the types is foreign.
+/// The branches are the same as with [`bijection!`].
+///
+/// # Usage
+/// ```text
+/// bijection_methods!(
+///     Foo { pub into_bar, pub from_bar },
+///     Bar { pub into_foo, pub from_foo },
+///     {
+///         Foo::A => Bar::X,
+///         // ...
+///     }
+/// );
+/// ```
+/// Each type may be followed by the names of its two methods (with an optional visibility):
+/// the first one converts `self` into the other type, the second one constructs `Self` from the other type.
+/// The first method takes `self` by value, so an `into_*` name follows the usual conventions.
+/// Types without names get no methods, which is how a foreign type is skipped.
+///
+/// The names must always be spelled out, as `macro_rules!` can't derive them from the type names.
+///
+/// A visibility before the first type applies to all of the methods, instead of giving one for each name:
+/// `bijection_methods!(pub(crate) Foo { into_bar, from_bar }, Bar { into_foo, from_foo }, { ... })`.
+/// The names may then not have a visibility of their own.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::bijection_methods;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Level {
+///     Low,
+///     High,
+/// }
+///
+/// // Option<bool> is foreign, so it only gets used by the methods of Level
+/// bijection_methods!(Level { pub into_flag, pub from_flag }, Option<bool>, {
+///     Level::High => Some(true),
+///     Level::Low => Some(false); reverse Some(false) | None => Level::Low,
+/// });
+///
+/// assert_eq!(Level::High.into_flag(), Some(true));
+/// assert_eq!(Level::from_flag(None), Level::Low);
+/// ```
+#[macro_export]
+macro_rules! bijection_methods {
+    // Entry
+    (
+        $first_ty:ty $({ $first_into_vis:vis $first_into:ident, $first_from_vis:vis $first_from:ident $(,)? })?,
+        $second_ty:ty $({ $second_into_vis:vis $second_into:ident, $second_from_vis:vis $second_from:ident $(,)? })?,
+        {$($bij:tt)*}
+    ) => {
+        $crate::bijection!(@
+            ([] ([] []) $first_ty, $second_ty, methods
+                [$($first_into_vis $first_into, $first_from_vis $first_from)?]
+                [$($second_into_vis $second_into, $second_from_vis $second_from)?]
+            )
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Entry (with a visibility for all methods)
+    // Ex: bijection_methods!(pub(crate) Foo { into_bar, from_bar }, Bar, { ... })
+    // Names with their own visibility don't match here, and are handled by the previous arm
+    (
+        $vis:vis
+        $first_ty:ty $({ $first_into:ident, $first_from:ident $(,)? })?,
+        $second_ty:ty $({ $second_into:ident, $second_from:ident $(,)? })?,
+        {$($bij:tt)*}
+    ) => {
+        $crate::bijection!(@
+            ([] ([] []) $first_ty, $second_ty, methods
+                [$($vis $first_into, $vis $first_from)?]
+                [$($vis $second_into, $vis $second_from)?]
+            )
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!(
+            "Expected: TypeA { into_b, from_b }, TypeB { into_a, from_a }, { /* bijection patterns */ }"
+        );
+    };
+}
+
+/// Generates `From` impls between three types, where the middle one links the other two.
+///
+/// # Usage
+/// ```text
+/// bijection_chain!(A, B, C, {
+///     a_b: { A::X => B::X, /* ... */ },
+///     b_c: { B::X => C::X, /* ... */ },
+/// });
+/// ```
+/// The `a_b` and `b_c` blocks are regular [`bijection!`] declaration blocks.
+/// Altogether, this generates six impls:
+/// - `From<A> for B` and `From<B> for A` from the `a_b` branches,
+/// - `From<B> for C` and `From<C> for B` from the `b_c` branches,
+/// - `From<A> for C` and `From<C> for A`, which convert through `B` by calling the impls above.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::bijection_chain;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Wire {
+///     Zero,
+///     One,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bit {
+///     Low,
+///     High,
+/// }
+///
+/// bijection_chain!(Wire, Bit, bool, {
+///     a_b: {
+///         Wire::Zero => Bit::Low,
+///         Wire::One => Bit::High,
+///     },
+///     b_c: {
+///         Bit::Low => false,
+///         Bit::High => true,
+///     },
+/// });
+///
+/// assert_eq!(bool::from(Wire::One), true);
+/// assert_eq!(Wire::from(false), Wire::Zero);
+/// ```
+#[macro_export]
+macro_rules! bijection_chain {
+    // Entry
+    ($first_ty:ty, $middle_ty:ty, $last_ty:ty, {
+        a_b: {$($first_bij:tt)*} $(,)?
+        b_c: {$($last_bij:tt)*} $(,)?
+    }) => {
+        $crate::bijection!($first_ty, $middle_ty, { $($first_bij)* });
+        $crate::bijection!($middle_ty, $last_ty, { $($last_bij)* });
+
+        #[automatically_derived]
+        impl ::core::convert::From<$first_ty> for $last_ty {
+            fn from(value: $first_ty) -> Self {
+                <$last_ty as ::core::convert::From<$middle_ty>>::from(<$middle_ty as ::core::convert::From<$first_ty>>::from(value))
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::From<$last_ty> for $first_ty {
+            fn from(value: $last_ty) -> Self {
+                <$first_ty as ::core::convert::From<$middle_ty>>::from(<$middle_ty as ::core::convert::From<$last_ty>>::from(value))
+            }
+        }
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: TypeA, TypeB, TypeC, { a_b: { /* patterns */ }, b_c: { /* patterns */ } }");
+    };
+}
+
+/// Generates conversions between two containers, converting them element by element.
+///
+/// # Usage
+/// ```text
+/// bijection_container!(Stack<Foo>, Stack<Bar>);
+/// bijection_container!(Convert, VecDeque<Foo>, VecDeque<Bar>);
+/// ```
+/// The elements are converted with `Into::into`, e.g. through the impls of a [`bijection!`] between them,
+/// and collected into the other container. For `Stack<Foo>` and `Stack<Bar>`, this requires:
+/// - `Foo: Into<Bar>` and `Bar: Into<Foo>`,
+/// - `Stack<Foo>: IntoIterator<Item = Foo>` and `Stack<Bar>: IntoIterator<Item = Bar>`,
+/// - `Stack<Foo>: FromIterator<Foo>` and `Stack<Bar>: FromIterator<Bar>`.
+///
+/// Without a trait, this generates `From<Stack<Foo>> for Stack<Bar>` and the reverse.
+/// The orphan rule only allows those for containers of your own, so standard ones like `Vec` or `VecDeque`
+/// need a conversion trait of your own instead, implemented like with [`bijection_trait!`].
+///
+/// # Examples
+/// ```rust
+/// use biject_into::{bijection, bijection_container};
+/// use std::collections::VecDeque;
+///
+/// trait Convert<T> {
+///     fn convert(self) -> T;
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// bijection_container!(Convert, VecDeque<Foo>, VecDeque<Bar>);
+///
+/// let bars: VecDeque<Bar> = VecDeque::from([Foo::A, Foo::B]).convert();
+/// assert_eq!(bars, [Bar::X, Bar::Y]);
+/// ```
+#[macro_export]
+macro_rules! bijection_container {
+    // Entry (trait impls)
+    // Ex: bijection_container!(Convert, VecDeque<Foo>, VecDeque<Bar>)
+    // A container type like `Stack<Foo>` stops matching the path at `<`, so it falls through to the entry below
+    ($($trait_seg:ident)::+, $first_ty:ty, $second_ty:ty $(,)?) => {
+        $crate::bijection_container!(@trait_impl [$($trait_seg)::+] $first_ty => $second_ty);
+        $crate::bijection_container!(@trait_impl [$($trait_seg)::+] $second_ty => $first_ty);
+    };
+
+    // Entry
+    ($first_ty:ty, $second_ty:ty $(,)?) => {
+        $crate::bijection_container!(@from_impl $first_ty => $second_ty);
+        $crate::bijection_container!(@from_impl $second_ty => $first_ty);
+    };
+
+    (@trait_impl [$($trait_seg:ident)::+] $self_ty:ty => $other_ty:ty) => {
+        #[automatically_derived]
+        impl $($trait_seg)::+ <$other_ty> for $self_ty {
+            fn convert(self) -> $other_ty {
+                ::core::iter::FromIterator::from_iter(::core::iter::Iterator::map(
+                    ::core::iter::IntoIterator::into_iter(self),
+                    ::core::convert::Into::into,
+                ))
+            }
+        }
+    };
+
+    (@from_impl $src_ty:ty => $dst_ty:ty) => {
+        #[automatically_derived]
+        impl ::core::convert::From<$src_ty> for $dst_ty {
+            fn from(value: $src_ty) -> Self {
+                ::core::iter::FromIterator::from_iter(::core::iter::Iterator::map(
+                    ::core::iter::IntoIterator::into_iter(value),
+                    ::core::convert::Into::into,
+                ))
+            }
+        }
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: ContainerA, ContainerB or Trait, ContainerA, ContainerB");
+    };
+}
+
+/// Implements a conversion trait of your own for any two types, instead of `From`.
+///
+/// # Usage
+/// ```text
+/// bijection_trait!(Convert, Foo, Bar, { /* bijection patterns */ });
+/// ```
+/// The trait is generic over the target type, and has a single `convert(self)` method by convention:
+/// ```text
+/// trait Convert<T> {
+///     fn convert(self) -> T;
+/// }
+/// ```
+/// This generates `impl Convert<Bar> for Foo` and `impl Convert<Foo> for Bar`,
+/// with the same branches as [`bijection!`]. The trait may be given as a path (e.g. `conv::Convert`),
+/// and can be sealed, as long as it's accessible where the macro is used.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::bijection_trait;
+///
+/// mod conv {
+///     mod sealed {
+///         pub trait Sealed {}
+///     }
+///
+///     pub trait Convert<T>: sealed::Sealed {
+///         fn convert(self) -> T;
+///     }
+///
+///     impl sealed::Sealed for super::Foo {}
+///     impl sealed::Sealed for super::Bar {}
+/// }
+///
+/// use conv::Convert;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Foo {
+///     A,
+///     B(u8),
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Bar {
+///     X,
+///     Y(u8),
+/// }
+///
+/// bijection_trait!(conv::Convert, Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B(n) => Bar::Y(n),
+/// });
+///
+/// fn main() {
+///     let bar: Bar = Foo::B(3).convert();
+///     assert_eq!(bar, Bar::Y(3));
+///     assert_eq!(Convert::<Foo>::convert(Bar::X), Foo::A);
+/// }
+/// ```
+#[macro_export]
+macro_rules! bijection_trait {
+    // Entry
+    ($($trait_seg:ident)::+, $first_ty:ty, $second_ty:ty, {$($bij:tt)*}) => {
+        $crate::bijection!(@
+            ([] ([] []) $first_ty, $second_ty, trait [$($trait_seg)::+])
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: Trait, TypeA, TypeB, { /* bijection patterns */ }");
+    };
+}
+
+/// Generates `From` impls between two enums which share the names of their unit variants.
+///
+/// # Usage
+/// ```text
+/// bijection_same_names!(Foo, Bar, [A, B, C]);
+/// ```
+/// Each listed name is expanded into a [`bijection!`] branch, e.g. `Foo::A => Bar::A`.
+/// Flags are passed on as well, e.g. `bijection_same_names!(#[by_ref] Foo, Bar, [A, B])`.
+///
+/// The enums are written as plain names (import them first if needed), as the variants are appended to them.
+/// Only unit variants can be listed; variants carrying data need the full form, so `A(x)` is rejected,
+/// and listing a data-carrying variant by its name fails to compile in the generated `match`.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::bijection_same_names;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum WireState {
+///     Idle,
+///     Busy,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum State {
+///     Idle,
+///     Busy,
+/// }
+///
+/// bijection_same_names!(WireState, State, [Idle, Busy]);
+///
+/// assert_eq!(State::from(WireState::Busy), State::Busy);
+/// assert_eq!(WireState::from(State::Idle), WireState::Idle);
+/// ```
+#[macro_export]
+macro_rules! bijection_same_names {
+    // Entry
+    ($(#[$($flag:tt)*])* $first_ty:ident, $second_ty:ident, [$($variant:ident),* $(,)?]) => {
+        $crate::bijection!($(#[$($flag)*])* $first_ty, $second_ty, {
+            $($first_ty::$variant => $second_ty::$variant,)*
+        });
+    };
+
+    // Ex: bijection_same_names!(Foo, Bar, [A, B(x)])
+    ($(#[$($flag:tt)*])* $first_ty:ident, $second_ty:ident, [$($variants:tt)*]) => {
+        compile_error!(concat!(
+            "Only unit variant names can be listed, write the full bijection! for variants with data (got: ",
+            stringify!($($variants)*),
+            ")"
+        ));
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: EnumA, EnumB, [ /* unit variant names */ ]");
+    };
+}
+
+/// Generates conversions between an enum and the indices of its unit variants.
+///
+/// # Usage
+/// ```text
+/// bijection_enum_index!(Op, u32, [Add, Sub, Mul, Div]);
+/// ```
+/// The variants are numbered in the order they are listed, starting at 0, so this is the same as
+/// `bijection!(Op, u32, { Op::Add => 0, Op::Sub => 1, Op::Mul => 2, Op::Div => 3 })`.
+/// Listing them in their declaration order gives the same numbers as their implicit discriminants
+/// (the macro can't check that, as it only sees the list).
+/// Like with any integer in [`bijection!`], the conversion into the integer is a `From` impl,
+/// and the one back is a `TryFrom` impl returning the unknown integer (or the `error = ...` type, given before the list).
+///
+/// The enum and the integer type are written as plain names, and flags are passed on to [`bijection!`].
+/// Up to 256 variants can be listed.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::bijection_enum_index;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Sub,
+///     Mul,
+///     Div,
+/// }
+///
+/// bijection_enum_index!(Op, u32, [Add, Sub, Mul, Div]);
+///
+/// assert_eq!(u32::from(Op::Mul), 2);
+/// assert_eq!(Op::try_from(3), Ok(Op::Div));
+/// assert_eq!(Op::try_from(4), Err(4));
+/// ```
+#[macro_export]
+macro_rules! bijection_enum_index {
+    // Entry
+    ($(#[$($flag:tt)*])* $enum_ty:ident, $int_ty:ident, $(error = $error_ty:ty,)? [$($variant:ident),* $(,)?]) => {
+        $crate::bijection_enum_index!(@assign $enum_ty
+            [$(#[$($flag)*])* $enum_ty, $int_ty, $(error = $error_ty,)?]
+            {}
+            [$($variant)*]
+            // Integer literals can't be computed, so the indices are taken from this list
+            [
+            0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+            16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+            32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+            48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+            64 65 66 67 68 69 70 71 72 73 74 75 76 77 78 79
+            80 81 82 83 84 85 86 87 88 89 90 91 92 93 94 95
+            96 97 98 99 100 101 102 103 104 105 106 107 108 109 110 111
+            112 113 114 115 116 117 118 119 120 121 122 123 124 125 126 127
+            128 129 130 131 132 133 134 135 136 137 138 139 140 141 142 143
+            144 145 146 147 148 149 150 151 152 153 154 155 156 157 158 159
+            160 161 162 163 164 165 166 167 168 169 170 171 172 173 174 175
+            176 177 178 179 180 181 182 183 184 185 186 187 188 189 190 191
+            192 193 194 195 196 197 198 199 200 201 202 203 204 205 206 207
+            208 209 210 211 212 213 214 215 216 217 218 219 220 221 222 223
+            224 225 226 227 228 229 230 231 232 233 234 235 236 237 238 239
+            240 241 242 243 244 245 246 247 248 249 250 251 252 253 254 255
+            ]
+        );
+    };
+
+    // Each variant takes the next index. Eight at a time, so long enums don't hit the recursion limit.
+    // The resulting branches are a single run of plain branches for bijection! (see "Runs of plain branches" there).
+    (@assign $enum_ty:ident $head:tt { $($done:tt)* }
+        [$v0:ident $v1:ident $v2:ident $v3:ident $v4:ident $v5:ident $v6:ident $v7:ident $($variant:ident)*]
+        [$i0:literal $i1:literal $i2:literal $i3:literal $i4:literal $i5:literal $i6:literal $i7:literal $($index:literal)*]
+    ) => {
+        $crate::bijection_enum_index!(@assign $enum_ty $head
+            {
+                $($done)*
+                $enum_ty::$v0 => $i0,
+                $enum_ty::$v1 => $i1,
+                $enum_ty::$v2 => $i2,
+                $enum_ty::$v3 => $i3,
+                $enum_ty::$v4 => $i4,
+                $enum_ty::$v5 => $i5,
+                $enum_ty::$v6 => $i6,
+                $enum_ty::$v7 => $i7,
+            }
+            [$($variant)*]
+            [$($index)*]
+        );
+    };
+    (@assign $enum_ty:ident $head:tt { $($done:tt)* } [$first:ident $($variant:ident)*] [$next:literal $($index:literal)*]) => {
+        $crate::bijection_enum_index!(@assign $enum_ty $head { $($done)* $enum_ty::$first => $next, } [$($variant)*] [$($index)*]);
+    };
+    (@assign $enum_ty:ident [$($head:tt)*] { $($done:tt)* } [] [$($index:literal)*]) => {
+        $crate::bijection!($($head)* { $($done)* });
+    };
+    (@assign $enum_ty:ident $head:tt $done:tt [$($variant:ident)+] []) => {
+        compile_error!(concat!("Too many variants for bijection_enum_index!, at most 256 can be listed (", stringify!($enum_ty), ")"));
+    };
+
+    // Ex: bijection_enum_index!(Op, u8, [Add, Sub(x)])
+    ($(#[$($flag:tt)*])* $enum_ty:ident, $int_ty:ident, $(error = $error_ty:ty,)? [$($variants:tt)*]) => {
+        compile_error!(concat!(
+            "Only unit variant names can be listed, write the full bijection! for variants with data (got: ",
+            stringify!($($variants)*),
+            ")"
+        ));
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: Enum, IntegerType, [ /* unit variant names */ ]");
+    };
+}
+
+/// Generates `Display` and `FromStr` impls for a type, mapping each value to a string literal.
+///
+/// # Usage
+/// ```text
+/// str_bijection!(Mode, {
+///     Mode::Fast => "fast",
+///     Mode::Slow => "slow",
+/// });
+/// ```
+/// The branches are the same as with [`bijection!`], with a string literal on each right-hand side.
+/// `Display` writes the literal for a value (respecting width and alignment, like `str` does),
+/// and `FromStr` matches the string against the literals.
+/// Not every string has a value, so parsing is fallible, and returns [`ParseError`] for anything else.
+///
+/// A different error type can be given with `error = ...` after the type (e.g. `str_bijection!(Mode, error = ModeError, { ... })`).
+/// Unknown strings are then converted with `From::from(s)`, so the error type must implement `From<&str>`.
+///
+/// # Examples
+/// ```rust
+/// use biject_into::{str_bijection, ParseError};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Mode {
+///     Fast,
+///     Slow,
+/// }
+///
+/// str_bijection!(Mode, {
+///     Mode::Fast => "fast",
+///     Mode::Slow => "slow",
+/// });
+///
+/// assert_eq!(Mode::Fast.to_string(), "fast");
+/// assert_eq!("slow".parse(), Ok(Mode::Slow));
+/// assert_eq!("medium".parse::<Mode>(), Err(ParseError));
+/// ```
+#[macro_export]
+macro_rules! str_bijection {
+    // Entry
+    ($ty:ty, {$($bij:tt)*}) => {
+        $crate::str_bijection!($ty, error = $crate::ParseError, { $($bij)* });
+    };
+
+    // Entry (with an error type)
+    // Ex: str_bijection!(Mode, error = ModeError, { ... })
+    ($ty:ty, error = $error_ty:ty, {$($bij:tt)*}) => {
+        $crate::bijection!(@
+            ([] ([] []) $ty, str, str $error_ty)
+            {}
+            {}
+            ($($bij)*)
+        );
+    };
+
+    // Fallback, catches everything else
+    ($($unknown:tt)*) => {
+        compile_error!("Expected: Type, { /* patterns => string literals */ }");
+    };
+}
+
+/// The error returned when parsing a string which no [`str_bijection!`] branch maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError;
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("unrecognized string")
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+impl From<&str> for ParseError {
+    fn from(_: &str) -> Self {
+        ParseError
+    }
+}
+
+/// The default error of [`try_bijection!`], holding the value which no branch maps.
+///
+/// `TryFrom<Foo> for Bar` returns a `BijectionError<Foo>`, and the reverse a `BijectionError<Bar>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BijectionError<T> {
+    /// The unmapped value.
+    pub value: T,
+}
+
+impl<T> BijectionError<T> {
+    /// Returns the unmapped value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Display for BijectionError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "no bijection branch for {:?}", self.value)
+    }
+}
+
+impl<T: core::fmt::Debug> core::error::Error for BijectionError<T> {}
+
+impl<T> From<T> for BijectionError<T> {
+    fn from(value: T) -> Self {
+        BijectionError { value }
+    }
+}
+
+/// A proc-macro version of [`bijection!`](crate::bijection!), with errors reported on the offending tokens.
+///
+/// Requires the `proc` feature, which adds dependencies on `syn`, `quote` and `proc-macro2`.
+///
+/// The declarative macros point every error at the whole invocation, as they can't do any better.
+/// `checked::bijection!` parses the branches first, and rejects or-patterns and duplicate right-hand sides
+/// right where they are written. The input is then handed over to [`bijection!`](crate::bijection!) unchanged,
+/// so the grammar is the same (and anything else is still reported by the declarative macro).
+/// Like the declarative check, duplicates are compared textually, and branches with attributes
+/// (such as `#[cfg(...)]`) are skipped.
+///
+/// # Examples
+/// ```rust
+/// # #[cfg(feature = "proc")]
+/// # fn main() {
+/// use biject_into::checked;
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Foo {
+///     A,
+///     B,
+/// }
+///
+/// #[derive(Debug, PartialEq, Clone)]
+/// enum Bar {
+///     X,
+///     Y,
+/// }
+///
+/// checked::bijection!(Foo, Bar, {
+///     Foo::A => Bar::X,
+///     Foo::B => Bar::Y,
+/// });
+///
+/// assert_eq!(Bar::from(Foo::B), Bar::Y);
+/// # }
+/// # #[cfg(not(feature = "proc"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "proc")]
+pub mod checked {
+    pub use crate::__checked_bijection as bijection;
+}
+
+// Hands `$crate` to the proc macro, which expands to `bijection!` through it,
+// so the path still resolves when the dependency is renamed
+#[cfg(feature = "proc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __checked_bijection {
+    ($($input:tt)*) => {
+        $crate::__private::checked_bijection! { ($crate) $($input)* }
+    };
+}
+
+// Stands in for the proc macro behind `#[ext]`, which can't name the methods without it
+#[cfg(not(feature = "proc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ext_without_proc {
+    ($($input:tt)*) => {
+        compile_error!(
+            "#[ext] needs the `proc` feature of biject_into, which names the extension methods"
+        );
+    };
+}
+
+// Picks the impls for `#[boxed]`, with or without the boxed ones
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __boxed_with_alloc {
+    ({ $($boxed:tt)* } { $($owned:tt)* }) => {
+        $($boxed)*
+    };
+}
+
+// Stands in for the above without `alloc`, where `Box` isn't available
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __boxed_without_alloc {
+    ({ $($boxed:tt)* } { $($owned:tt)* }) => {
+        compile_error!("#[boxed] requires the `alloc` feature of biject_into");
+        $($owned)*
+    };
+}
+
+// Not public API, only used by the macros
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use crate::__boxed_with_alloc as boxed;
+    #[cfg(not(feature = "alloc"))]
+    pub use crate::__boxed_without_alloc as boxed;
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+
+    #[cfg(not(feature = "proc"))]
+    pub use crate::__ext_without_proc as ext;
+    #[cfg(feature = "proc")]
+    pub use biject_into_proc::bijection as checked_bijection;
+    #[cfg(feature = "proc")]
+    pub use biject_into_proc::ext;
+
+    /// Wraps the matched value of an empty bijection block. For any type with values, the non-exhaustive `match` error
+    /// notes where this is defined, so the hint on that line is shown along with it.
+    #[rustfmt::skip]
+    pub struct EmptyBijectionBlock<T>(pub T); // The bijection block is empty - did you forget the branches?
+
+    pub const DUPLICATE_RHS: &str = "Duplicate right-hand side in bijection branches:";
+    pub const DUPLICATE_LHS: &str = "Duplicate left-hand side in bijection branches:";
+
+    /// Byte-wise string comparison, usable in const contexts.
+    pub const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Finds the first pair of enabled branches with the same key (the first element of each tuple).
+    pub const fn find_duplicate(branches: &[(&str, &str, bool)]) -> Option<(usize, usize)> {
+        let mut second = 1;
+        while second < branches.len() {
+            let mut first = 0;
+            while first < second {
+                if branches[first].2
+                    && branches[second].2
+                    && str_eq(branches[first].0, branches[second].0)
+                {
+                    return Some((first, second));
+                }
+                first += 1;
+            }
+            second += 1;
+        }
+        None
+    }
+
+    pub const fn duplicate_message_len(
+        header: &str,
+        branches: &[(&str, &str, bool)],
+        duplicate: Option<(usize, usize)>,
+    ) -> usize {
+        match duplicate {
+            // Header, then both branches on separate lines
+            Some((first, second)) => {
+                header.len() + 1 + branches[first].1.len() + 1 + branches[second].1.len()
+            }
+            None => 0,
+        }
+    }
+
+    pub const fn duplicate_message<const LEN: usize>(
+        header: &str,
+        branches: &[(&str, &str, bool)],
+        duplicate: Option<(usize, usize)>,
+    ) -> [u8; LEN] {
+        let mut message = [0; LEN];
+        if let Some((first, second)) = duplicate {
+            let mut len = write(&mut message, 0, header);
+            len = write(&mut message, len, "\n");
+            len = write(&mut message, len, branches[first].1);
+            len = write(&mut message, len, "\n");
+            write(&mut message, len, branches[second].1);
+        }
+        message
+    }
+
+    /// Finds the first pattern containing a group (i.e. one which binds data), skipping string and char literals.
+    pub const fn find_group(patterns: &[&str]) -> Option<usize> {
+        let mut i = 0;
+        while i < patterns.len() {
+            let bytes = patterns[i].as_bytes();
+            let mut quote = None;
+            let mut j = 0;
+            while j < bytes.len() {
+                match (quote, bytes[j]) {
+                    (Some(_), b'\\') => j += 1,
+                    (Some(q), c) if c == q => quote = None,
+                    (Some(_), _) => {}
+                    (None, c @ (b'"' | b'\'')) => quote = Some(c),
+                    (None, b'(' | b'[' | b'{') => return Some(i),
+                    (None, _) => {}
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        None
+    }
+
+    pub const fn message_len(parts: &[&str]) -> usize {
+        let mut len = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            len += parts[i].len();
+            i += 1;
+        }
+        len
+    }
+
+    /// Concatenates the parts into a fixed-size buffer, as const panics can't format more than a single `&str`.
+    pub const fn message<const LEN: usize>(parts: &[&str]) -> [u8; LEN] {
+        let mut message = [0; LEN];
+        let mut len = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            len = write(&mut message, len, parts[i]);
+            i += 1;
+        }
+        message
+    }
+
+    const fn write(buffer: &mut [u8], start: usize, text: &str) -> usize {
+        let text = text.as_bytes();
+        let mut i = 0;
+        while i < text.len() {
+            buffer[start + i] = text[i];
+            i += 1;
+        }
+        start + text.len()
+    }
+
+    pub const fn as_str(bytes: &[u8]) -> &str {
+        match core::str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use crate::BijectionError;
+
+    mod context_usage_tests {
+        #[allow(dead_code)]
+        mod context_mod {
+            enum Foo {
+                A,
+                B,
+            }
+            enum Bar {
+                X,
+                Y,
+            }
+
+            // Can be used within modules
+            bijection!(Foo, Bar, {
+                Foo::A => Bar::X,
+                Foo::B => Bar::Y,
+            });
+        }
+
+        mod context_fn {
+            #[allow(dead_code)]
+            fn foo() {
+                enum Foo {
+                    A,
+                    B,
+                }
+                enum Bar {
+                    X,
+                    Y,
+                }
+
+                // Can be used within functions
+                bijection!(Foo, Bar, {
+                    Foo::A => Bar::X,
+                    Foo::B => Bar::Y,
+                });
+            }
+        }
+    }
+
+    /// Asserts equality in both directions,
+    /// and that `T` and `U` both implement `From`/`Into` for one another.
+    fn test_bijection_eq<T, U>(t: T, u: U)
+    where
+        T: From<U> + PartialEq + Debug + Clone,
+        U: From<T> + PartialEq + Debug + Clone,
+    {
+        assert_eq!(U::from(t.clone()), u.clone());
+        assert_eq!(T::from(u), t);
+    }
+
+    #[test]
+    fn empty_enum() {
+        #[allow(dead_code)]
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {}
+
+        #[allow(dead_code)]
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {}
+
+        bijection!(Foo, Bar, {});
+    }
+
+    #[test]
+    fn one_variant() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+    }
+
+    #[test]
+    fn two_variants() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B, Bar::Y);
+    }
+
+    #[test]
+    fn struct_point() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct PointFlipped {
+            y: i32,
+            x: i32,
+        }
+
+        bijection!(Point, PointFlipped, {
+            Point { x, y } => PointFlipped { y: x, x: y }
+        });
+
+        test_bijection_eq(Point { x: 5, y: 10 }, PointFlipped { x: 10, y: 5 });
+        test_bijection_eq(Point { x: 20, y: -20 }, PointFlipped { x: -20, y: 20 });
+    }
+
+    #[test]
+    fn struct_enum() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum PointEnum {
+            Zero,
+            OneOne,
+            Other { x: i32, y: i32 },
+        }
+
+        bijection!(Point, PointEnum, {
+            Point { x: 0, y: 0 } => PointEnum::Zero,
+            Point { x: 1, y: 1 } => PointEnum::OneOne,
+            Point { x, y } => PointEnum::Other { x, y },
+        });
+
+        test_bijection_eq(Point { x: 0, y: 0 }, PointEnum::Zero);
+        test_bijection_eq(Point { x: 1, y: 1 }, PointEnum::OneOne);
+        test_bijection_eq(Point { x: 5, y: 10 }, PointEnum::Other { x: 5, y: 10 });
+        test_bijection_eq(Point { x: 20, y: -20 }, PointEnum::Other { x: 20, y: -20 });
+    }
+
+    #[test]
+    fn tuple_endpoints() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Color {
+            r: u8,
+            g: u8,
+            b: u8,
+        }
+
+        bijection!((i32, i32), Point, {
+            (x, y) => Point { x, y },
+        });
+
+        bijection!(Color, (u8, u8, u8), {
+            Color { r, g, b } => (r, g, b),
+        });
+
+        test_bijection_eq((1, -2), Point { x: 1, y: -2 });
+        test_bijection_eq(Color { r: 1, g: 2, b: 3 }, (1, 2, 3));
+    }
+
+    #[test]
+    fn tuple_endpoint_auto_into() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        struct Channel(u8);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Rgb {
+            r: Channel,
+            g: Channel,
+            b: Channel,
+        }
+
+        bijection!(u8, Channel, {
+            c => Channel(c),
+        });
+
+        bijection!(#[auto_into] Rgb, (u8, u8, u8), {
+            Rgb { r, g, b } => (r, g, b),
+        });
+
+        test_bijection_eq(
+            Rgb {
+                r: Channel(10),
+                g: Channel(20),
+                b: Channel(30),
+            },
+            (10, 20, 30),
+        );
+
+        let Rgb { r, g, b } = Rgb::from((1, 2, 3));
+        assert_eq!((r, g, b), (Channel(1), Channel(2), Channel(3)));
+    }
+
+    #[test]
+    fn array_endpoints() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Pair {
+            a: u8,
+            b: u8,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Triple {
+            a: u8,
+            b: u8,
+            c: u8,
+        }
+
+        bijection!([u8; 2], Pair, {
+            [a, b] => Pair { a, b },
+        });
+
+        bijection!(Triple, [u8; 3], {
+            Triple { a, b, c } => [a, b, c],
+        });
+
+        test_bijection_eq([1, 2], Pair { a: 1, b: 2 });
+        test_bijection_eq(Triple { a: 1, b: 2, c: 3 }, [1, 2, 3]);
+    }
+
+    #[test]
+    fn array_literal_elements() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Pair {
+            Zero(u8),
+            Other(u8, u8),
+        }
+
+        bijection!([u8; 2], Pair, {
+            [0, x] => Pair::Zero(x),
+            [a, b] => Pair::Other(a, b),
+        });
+
+        test_bijection_eq([0, 5], Pair::Zero(5));
+        test_bijection_eq([3, 4], Pair::Other(3, 4));
+    }
+
+    #[test]
+    fn struct_renamed_fields() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct A {
+            first: i32,
+            second: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct B {
+            a: i32,
+            b: i32,
+        }
+
+        bijection!(A, B, {
+            A { first: x, second: y } => B { a: x, b: y },
+        });
+
+        test_bijection_eq(
+            A {
+                first: 1,
+                second: 2,
+            },
+            B { a: 1, b: 2 },
+        );
+    }
+
+    #[test]
+    fn struct_nested() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Inner {
+            v: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Outer {
+            inner: Inner,
+            tag: u8,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Flat {
+            value: i32,
+            tag: u8,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Deep {
+            Zero { outer: Outer },
+            Other(Inner, (u8, Inner)),
+        }
+
+        bijection!(Outer, Flat, {
+            Outer { inner: Inner { v }, tag } => Flat { value: v, tag },
+        });
+
+        test_bijection_eq(
+            Outer {
+                inner: Inner { v: 3 },
+                tag: 1,
+            },
+            Flat { value: 3, tag: 1 },
+        );
+
+        // Nested on both sides, with renamed fields on each level
+        bijection!(Deep, (Flat, i32), {
+            Deep::Zero { outer: Outer { inner: Inner { v: value }, tag } } => (Flat { value, tag }, 0),
+            Deep::Other(Inner { v: first }, (tag, Inner { v: second })) => (Flat { value: first, tag }, second),
+        });
+
+        test_bijection_eq(
+            Deep::Zero {
+                outer: Outer {
+                    inner: Inner { v: 5 },
+                    tag: 2,
+                },
+            },
+            (Flat { value: 5, tag: 2 }, 0),
+        );
+        test_bijection_eq(
+            Deep::Other(Inner { v: 5 }, (2, Inner { v: 7 })),
+            (Flat { value: 5, tag: 2 }, 7),
+        );
+    }
+
+    #[test]
+    fn unreachable_patterns() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo(i32);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Bar(i32);
+
+        // This should complain with a warning! (hence the deny attribute)
+        // Bar(0x0) is not textually identical to Bar(0), so it's not rejected by the macro itself
+        #[deny(unfulfilled_lint_expectations)]
+        #[expect(unreachable_patterns)]
+        {
+            bijection!(Foo, Bar, {
+                Foo(0) => Bar(0),
+                Foo(1) => Bar(0x0),
+                Foo(1) => Bar(1),
+                Foo(x) => Bar(x),
+            });
+        }
+
+        assert_eq!(Bar::from(Foo(0)), Bar(0));
+        assert_eq!(Foo::from(Bar(0)), Foo(0));
+
+        assert_eq!(Bar::from(Foo(1)), Bar(0));
+        assert_ne!(Foo::from(Bar(1)), Foo(0));
+        assert_eq!(Foo::from(Bar(1)), Foo(1));
+
+        assert_eq!(Bar::from(Foo(3)), Bar(3));
+        assert_eq!(Foo::from(Bar(3)), Foo(3));
+    }
+
+    #[test]
+    fn external_type() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Tristate {
+            Neutral,
+            Positive,
+            Negative,
+        }
+
+        bijection!(Option<bool>, Tristate, {
+            None => Tristate::Neutral,
+            Some(true) => Tristate::Positive,
+            Some(false) => Tristate::Negative,
+        });
+
+        test_bijection_eq(Tristate::Neutral, None::<bool>);
+        test_bijection_eq(Tristate::Positive, Some(true));
+        test_bijection_eq(Tristate::Negative, Some(false));
+    }
+
+    #[test]
+    fn bidirectional_arrow() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C { x: i8 },
+            D(i8, i8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z { x: i8 },
+            Pair(i8, i8),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A <=> Bar::X,
+            Foo::B(b) => Bar::Y(b),
+            Foo::C { x } <=> Bar::Z { x };
+            Foo::D(a, b) <=> Bar::Pair(b, a),
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(2), Bar::Y(2));
+        test_bijection_eq(Foo::C { x: -3 }, Bar::Z { x: -3 });
+        test_bijection_eq(Foo::D(4, 5), Bar::Pair(5, 4));
+
+        // Literals on the left-hand side
+        #[derive(Debug, PartialEq)]
+        enum Sign {
+            Negative,
+            Zero,
+            Positive,
+        }
+
+        try_bijection!(i8, Sign, {
+            -1 <=> Sign::Negative,
+            0 <=> Sign::Zero,
+            1 => Sign::Positive,
+        });
+
+        assert_eq!(Sign::try_from(-1i8), Ok(Sign::Negative));
+        assert_eq!(Sign::try_from(0i8), Ok(Sign::Zero));
+        assert_eq!(i8::try_from(Sign::Positive), Ok(1));
+        assert!(Sign::try_from(2i8).is_err());
+    }
+
+    #[test]
+    fn block_first() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!({
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        } for Foo, Bar);
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(3), Bar::Y(3));
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Wrapper<T>(T);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Other<T>(T);
+
+        bijection!({ Wrapper(x) => Other(x) } for #[by_ref] <T: Copy> Wrapper<T>, Other<T> where T: Default);
+
+        test_bijection_eq(Wrapper(1), Other(1));
+        assert_eq!(Other::from(&Wrapper('a')), Other('a'));
+
+        bijection!({ Bar::X => 0, Bar::Y(_) => 1 } for Bar => u8);
+
+        assert_eq!(u8::from(Bar::Y(5)), 1);
+    }
+
+    #[test]
+    fn tuple_packing() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Pair(u8, char),
+            Triple(i8, i8, i8),
+            Empty,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Single((u8, char)),
+            Nested(i8, (i8, i8)),
+            Unit(()),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Pair(a, b) => Bar::Single((a, b)),
+            Foo::Triple(x, y, z) => Bar::Nested(x, (y, z)),
+            Foo::Empty => Bar::Unit(()),
+        });
+
+        test_bijection_eq(Foo::Pair(1, 'a'), Bar::Single((1, 'a')));
+        test_bijection_eq(Foo::Triple(-1, 0, 1), Bar::Nested(-1, (0, 1)));
+        test_bijection_eq(Foo::Empty, Bar::Unit(()));
+    }
+
+    #[test]
+    fn builder_expression() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(u8),
+            B(u8, bool),
+        }
+
+        #[derive(Debug, PartialEq, Clone, Default)]
+        struct Bar {
+            x: u8,
+            flag: bool,
+        }
+
+        #[derive(Default)]
+        struct BarBuilder {
+            x: u8,
+            flag: bool,
+        }
+
+        impl Bar {
+            fn builder() -> BarBuilder {
+                BarBuilder::default()
+            }
+        }
+
+        impl BarBuilder {
+            fn x(self, x: u8) -> Self {
+                BarBuilder { x, ..self }
+            }
+
+            fn flag(self, flag: bool) -> Self {
+                BarBuilder { flag, ..self }
+            }
+
+            fn build(self) -> Bar {
+                Bar {
+                    x: self.x,
+                    flag: self.flag,
+                }
+            }
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A(x) => Bar::builder().x(x).build(); reverse Bar { x, flag: false } => Foo::A(x),
+            Foo::B(x, flag) => Bar::builder().x(x).flag(flag).build(); reverse Bar { x, flag: true } => Foo::B(x, true),
+        });
+
+        test_bijection_eq(Foo::A(1), Bar { x: 1, flag: false });
+        test_bijection_eq(Foo::B(2, true), Bar { x: 2, flag: true });
+        assert_eq!(Foo::from(Bar { x: 3, flag: false }), Foo::A(3));
+    }
+
+    #[test]
+    fn forward_only_block() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            Legacy(u8),
+            Old,
+            Older,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[strict] Foo, Bar, {
+            Foo::A => Bar::X,
+            forward_only {
+                Foo::Old | Foo::Older => Bar::X,
+                Foo::Legacy(n) if n > 9 => Bar::Y(n - 10),
+                Foo::Legacy(n) => Bar::Y(n)
+            }
+            Foo::B(n) => Bar::Y(n),
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(3), Bar::Y(3));
+        assert_eq!(Bar::from(Foo::Old), Bar::X);
+        assert_eq!(Bar::from(Foo::Older), Bar::X);
+        assert_eq!(Bar::from(Foo::Legacy(12)), Bar::Y(2));
+        assert_eq!(Bar::from(Foo::Legacy(4)), Bar::Y(4));
+    }
+
+    #[test]
+    fn external_result() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Either {
+            Left(i32),
+            Right(String),
+        }
+
+        bijection!(Result<i32, String>, Either, {
+            Ok(v) => Either::Left(v),
+            Err(e) => Either::Right(e),
+        });
+
+        test_bijection_eq(Ok::<i32, String>(-3), Either::Left(-3));
+        test_bijection_eq(
+            Err::<i32, String>(String::from("nope")),
+            Either::Right(String::from("nope")),
+        );
+    }
+
+    #[test]
+    fn explicit_reverse() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(u8),
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(char),
+            Y,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A(n) => {
+                let digit = char::from_digit(n as u32, 10).unwrap_or('?');
+                Bar::X(digit)
+            }; reverse Bar::X(c) => Foo::A(c.to_digit(10).map_or(u8::MAX, |d| d as u8)),
+            Foo::B => Bar::Y
+        });
+
+        test_bijection_eq(Foo::A(0), Bar::X('0'));
+        test_bijection_eq(Foo::A(7), Bar::X('7'));
+        test_bijection_eq(Foo::B, Bar::Y);
+
+        assert_eq!(Bar::from(Foo::A(12)), Bar::X('?'));
+        assert_eq!(Foo::from(Bar::X('?')), Foo::A(u8::MAX));
+    }
+
+    #[test]
+    fn inverse_function() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Duration {
+            Minutes(u32),
+            Forever,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Timeout {
+            Seconds(u32),
+            Never,
+        }
+
+        fn minutes_to_seconds(minutes: u32) -> u32 {
+            minutes * 60
+        }
+
+        fn seconds_to_minutes(seconds: u32) -> u32 {
+            seconds / 60
+        }
+
+        bijection!(Duration, Timeout, {
+            Duration::Minutes(m) => Timeout::Seconds(minutes_to_seconds(m)) <=> seconds_to_minutes;
+            Duration::Forever => Timeout::Never,
+        });
+
+        test_bijection_eq(Duration::Minutes(3), Timeout::Seconds(180));
+        test_bijection_eq(Duration::Forever, Timeout::Never);
+    }
+
+    #[test]
+    fn inverse_function_paths() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Hours(u32);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Seconds(u32);
+
+        mod hours {
+            pub fn to_seconds(hours: u32) -> u32 {
+                hours * 3600
+            }
+
+            pub fn from_seconds(seconds: u32) -> u32 {
+                seconds / 3600
+            }
+        }
+
+        bijection!(Hours, Seconds, {
+            Hours(h) => Seconds(hours::to_seconds(h)) <=> hours::from_seconds
+        });
+
+        test_bijection_eq(Hours(2), Seconds(7200));
+    }
+
+    #[test]
+    fn inverse_closure() {
+        use core::time::Duration;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Timestamp(u64),
+            Millis(u64),
+            Never,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Time(Duration),
+            Exact(Duration),
+            Never,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Timestamp(secs) => Bar::Time(Duration::from_secs(secs)) <=> |d| d.as_secs(),
+            Foo::Millis(ms) => Bar::Exact(Duration::from_millis(ms)) <=> |d: Duration| d.as_millis() as u64,
+            Foo::Never => Bar::Never,
+        });
+
+        test_bijection_eq(Foo::Timestamp(90), Bar::Time(Duration::from_secs(90)));
+        test_bijection_eq(Foo::Millis(1500), Bar::Exact(Duration::from_millis(1500)));
+        test_bijection_eq(Foo::Never, Bar::Never);
+    }
+
+    #[test]
+    fn inverse_cast() {
+        trait Reinterpret<T> {
+            fn convert(self) -> T;
+        }
+
+        fn to_signed(x: u16) -> i16 {
+            x as i16
+        }
+
+        bijection_trait!(Reinterpret, i8, u8, {
+            n => n as u8 <=> |x| x as i8,
+        });
+
+        bijection_trait!(Reinterpret, i16, u16, {
+            n => n as u16 <=> to_signed
+        });
+
+        for n in i8::MIN..=i8::MAX {
+            let unsigned: u8 = n.convert();
+            assert_eq!(unsigned, n as u8);
+            assert_eq!(Reinterpret::<i8>::convert(unsigned), n);
+        }
+        assert_eq!(Reinterpret::<u8>::convert(-1i8), 255);
+        assert_eq!(Reinterpret::<i8>::convert(255u8), -1);
+        assert_eq!(Reinterpret::<u16>::convert(i16::MIN), 0x8000);
+        assert_eq!(Reinterpret::<i16>::convert(u16::MAX), -1);
+    }
+
+    #[test]
+    fn field_casts() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            N(u32, u32),
+            Named { x: u32, y: u32 },
+            Zero,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            N(u64, u32),
+            Named { x: u64, y: u64 },
+            Zero,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::N(a as u64, b) => Bar::N(a, b),
+            Foo::Named { x as u64, y: w as u64 } => Bar::Named { x, y: w },
+            Foo::Zero => Bar::Zero,
+        });
+
+        test_bijection_eq(Foo::N(u32::MAX, 1), Bar::N(u64::from(u32::MAX), 1));
+        test_bijection_eq(Foo::Named { x: 2, y: 3 }, Bar::Named { x: 2, y: 3 });
+        test_bijection_eq(Foo::Zero, Bar::Zero);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Narrow {
+            Id(u32),
+            None,
+        }
+
+        // An explicit reverse goes through the branches one by one
+        bijection!(Narrow, Option<u64>, {
+            Narrow::Id(id as u64) => Some(id),
+            Narrow::None => None; reverse None => Narrow::None,
+        });
+
+        test_bijection_eq(Narrow::Id(u32::MAX), Some(u64::from(u32::MAX)));
+        test_bijection_eq(Narrow::None, None);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Mixed {
+            N(u32),
+            S(Option<u8>),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Wide {
+            N(u64),
+            S(Option<u8>),
+        }
+
+        // Nested patterns next to a hinted branch
+        bijection!(Mixed, Wide, {
+            Mixed::N(n as u64) => Wide::N(n),
+            Mixed::S(Some(x)) => Wide::S(Some(x)),
+            Mixed::S(None) => Wide::S(None),
+        });
+
+        test_bijection_eq(Mixed::N(u32::MAX), Wide::N(u64::from(u32::MAX)));
+        test_bijection_eq(Mixed::S(Some(3)), Wide::S(Some(3)));
+        test_bijection_eq(Mixed::S(None), Wide::S(None));
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Small {
+            V0(u8),
+            V1(u8),
+            V2(u8),
+            V3(u8),
+            V4(u8),
+            V5(u8),
+            V6(u8),
+            V7(u8),
+            Other,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Big {
+            V0(u32),
+            V1(u32),
+            V2(u32),
+            V3(u32),
+            V4(u32),
+            V5(u32),
+            V6(u32),
+            V7(u32),
+            Other,
+        }
+
+        // Hinted branches are taken one at a time, up to the run ending with an explicit reverse
+        bijection!(Small, Big, {
+            Small::V0(n as u32) => Big::V0(n),
+            Small::V1(n as u32) => Big::V1(n),
+            Small::V2(n as u32) => Big::V2(n),
+            Small::V3(n as u32) => Big::V3(n),
+            Small::V4(n as u32) => Big::V4(n),
+            Small::V5(n as u32) => Big::V5(n),
+            Small::V6(n as u32) => Big::V6(n),
+            Small::V7(n as u32) => Big::V7(n),
+            Small::Other => Big::Other; reverse Big::Other => Small::Other,
+        });
+
+        test_bijection_eq(Small::V0(1), Big::V0(1));
+        test_bijection_eq(Small::V7(u8::MAX), Big::V7(255));
+        test_bijection_eq(Small::Other, Big::Other);
+    }
+
+    #[test]
+    fn ref_bindings() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Name(String),
+            Entry { key: String, value: u8 },
+            Pair(String),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Label(String),
+            Entry { k: String, value: u8 },
+            Pair { key: String },
+        }
+
+        bijection!(#[by_ref] Foo, Bar, {
+            Foo::Name(ref s) => Bar::Label(ref s),
+            Foo::Entry { ref key, value } => Bar::Entry { k: ref key, value },
+            Foo::Pair(ref key) => Bar::Pair { ref key },
+        });
+
+        let entry = Foo::Entry {
+            key: String::from("a"),
+            value: 3,
+        };
+        assert_eq!(
+            Bar::from(&entry),
+            Bar::Entry {
+                k: String::from("a"),
+                value: 3
+            }
+        );
+        test_bijection_eq(Foo::Name(String::from("b")), Bar::Label(String::from("b")));
+        test_bijection_eq(
+            Foo::Pair(String::from("c")),
+            Bar::Pair {
+                key: String::from("c"),
+            },
+        );
+    }
+
+    #[test]
+    fn explicit_reverse_guard() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Val(i32),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Positive(i32),
+            Other(i32),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Val(n) if n > 0 => Bar::Positive(n); reverse Bar::Positive(n) => Foo::Val(n),
+            Foo::Val(n) => Bar::Other(n),
+        });
+
+        test_bijection_eq(Foo::Val(5), Bar::Positive(5));
+        test_bijection_eq(Foo::Val(0), Bar::Other(0));
+        test_bijection_eq(Foo::Val(-5), Bar::Other(-5));
+    }
+
+    #[test]
+    fn mixed_separators() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C,
+            D(u8),
+            E,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z,
+            W(u8),
+            V,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X;
+            Foo::B(b) => Bar::Y(b),
+            Foo::C => Bar::Z;
+            Foo::D(d) => {
+                Bar::W(d)
+            }; reverse Bar::W(d) => {
+                Foo::D(d)
+            }
+            Foo::E => Bar::V;
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        test_bijection_eq(Foo::C, Bar::Z);
+        test_bijection_eq(Foo::D(2), Bar::W(2));
+        test_bijection_eq(Foo::E, Bar::V);
+    }
+
+    #[test]
+    fn mixed_separators_literals() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+            C,
+        }
+
+        bijection!(Foo, u8, {
+            Foo::A => 0;
+            Foo::B => 1,
+            Foo::C => 2; reverse 2 => Foo::C;
+        });
+
+        assert_eq!(u8::from(Foo::A), 0);
+        assert_eq!(Foo::try_from(1), Ok(Foo::B));
+        assert_eq!(Foo::try_from(2), Ok(Foo::C));
+        assert_eq!(Foo::try_from(3), Err(3));
+    }
+
+    #[test]
+    fn forward_only() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+            C(i32),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(i32),
+        }
+
+        // The reverse direction would be ambiguous (and Bar::Y(n + 1) is not a pattern)
+        #[deny(unreachable_patterns)]
+        {
+            bijection!(Foo => Bar, {
+                Foo::A => Bar::X,
+                Foo::B => Bar::X,
+                Foo::C(n) => Bar::Y(n + 1),
+            });
+        }
+
+        assert_eq!(Bar::from(Foo::A), Bar::X);
+        assert_eq!(Bar::from(Foo::B), Bar::X);
+        assert_eq!(Bar::from(Foo::C(1)), Bar::Y(2));
+    }
+
+    #[test]
+    fn reverse_only() {
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z(u8),
+        }
+
+        // Foo::B(b) is mapped twice, so only the reverse direction is a function
+        bijection!(Foo <= Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+            Foo::B(b) => Bar::Z(b),
+        });
+
+        assert_eq!(Foo::from(Bar::X), Foo::A);
+        assert_eq!(Foo::from(Bar::Y(1)), Foo::B(1));
+        assert_eq!(Foo::from(Bar::Z(2)), Foo::B(2));
+
+        mod nested {
+            #[derive(Debug, PartialEq)]
+            pub enum Level {
+                Low,
+                High,
+            }
+        }
+
+        bijection!(#[inline] nested::Level <= u8, {
+            nested::Level::Low => 0,
+            // Integers aren't matched exhaustively, so the last one needs a wildcard
+            nested::Level::High => _,
+        });
+
+        assert_eq!(nested::Level::from(0), nested::Level::Low);
+        assert_eq!(nested::Level::from(7), nested::Level::High);
+
+        #[derive(Debug, PartialEq)]
+        struct Flags(u8);
+
+        // The left-hand sides are only expressions, so they may compute the value
+        bijection!(Flags <= Bar, {
+            Flags(0) => Bar::X,
+            Flags(n | 0x80) => Bar::Y(n),
+            Flags(n + 1) => Bar::Z(n) if n < u8::MAX,
+            Flags(0xff) => Bar::Z(_),
+        });
+
+        assert_eq!(Flags::from(Bar::X), Flags(0));
+        assert_eq!(Flags::from(Bar::Y(1)), Flags(0x81));
+        assert_eq!(Flags::from(Bar::Z(1)), Flags(2));
+        assert_eq!(Flags::from(Bar::Z(u8::MAX)), Flags(0xff));
+    }
+
+    #[test]
+    fn forward_only_or_pattern() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo(i32);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Listed,
+            Other,
+        }
+
+        bijection!(Foo => Bar, {
+            Foo(0 | 2 | 4) => Bar::Listed,
+            Foo(_) => Bar::Other
+        });
+
+        assert_eq!(Bar::from(Foo(0)), Bar::Listed);
+        assert_eq!(Bar::from(Foo(4)), Bar::Listed);
+        assert_eq!(Bar::from(Foo(3)), Bar::Other);
+    }
+
+    #[test]
+    fn try_partial_mapping() {
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            A,
+            B,
+            C,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Error {
+            Foo(Foo),
+            Bar(Bar),
+        }
+
+        impl From<Foo> for Error {
+            fn from(value: Foo) -> Self {
+                Error::Foo(value)
+            }
+        }
+
+        impl From<Bar> for Error {
+            fn from(value: Bar) -> Self {
+                Error::Bar(value)
+            }
+        }
+
+        // Foo::C has no counterpart, so only Foo -> Bar can fail
+        try_bijection!(Foo, Bar, Error, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        fn convert(foo: Foo) -> Result<Bar, Error> {
+            let bar = Bar::try_from(foo)?;
+            Ok(bar)
+        }
+
+        assert_eq!(convert(Foo::A), Ok(Bar::X));
+        assert_eq!(convert(Foo::B), Ok(Bar::Y));
+        assert_eq!(convert(Foo::C), Err(Error::Foo(Foo::C)));
+
+        assert_eq!(Foo::try_from(Bar::X), Ok(Foo::A));
+        assert_eq!(Foo::try_from(Bar::Y), Ok(Foo::B));
+    }
+
+    #[test]
+    fn try_partial_both_ways() {
+        #[derive(Debug, PartialEq)]
+        struct Foo(i32);
+
+        #[derive(Debug, PartialEq)]
+        struct Bar(i32);
+
+        #[derive(Debug, PartialEq)]
+        struct Error(i32);
+
+        impl From<Foo> for Error {
+            fn from(value: Foo) -> Self {
+                Error(value.0)
+            }
+        }
+
+        impl From<Bar> for Error {
+            fn from(value: Bar) -> Self {
+                Error(value.0)
+            }
+        }
+
+        try_bijection!(Foo, Bar, Error, {
+            Foo(0) => Bar(10),
+            Foo(1) => Bar(11),
+        });
+
+        assert_eq!(Bar::try_from(Foo(0)), Ok(Bar(10)));
+        assert_eq!(Bar::try_from(Foo(1)), Ok(Bar(11)));
+        assert_eq!(Bar::try_from(Foo(2)), Err(Error(2)));
+
+        assert_eq!(Foo::try_from(Bar(10)), Ok(Foo(0)));
+        assert_eq!(Foo::try_from(Bar(11)), Ok(Foo(1)));
+        assert_eq!(Foo::try_from(Bar(0)), Err(Error(0)));
+    }
+
+    #[test]
+    fn by_ref_unit_variants() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        bijection!(#[by_ref] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        let foo = Foo::B;
+        let bar = Bar::X;
+        assert_eq!(Bar::from(&foo), Bar::Y);
+        assert_eq!(Foo::from(&bar), Foo::A);
+
+        // The owned impls are still generated
+        test_bijection_eq(foo, Bar::Y);
+        test_bijection_eq(Foo::A, bar);
+    }
+
+    #[test]
+    fn by_ref_data_variants() {
+        // Neither type is Copy, only the bound fields are
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C { x: i32, y: i32 },
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z { x: i32, y: i32 },
+        }
+
+        bijection!(#[by_ref] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+            Foo::C { x, y } => Bar::Z { x, y },
+        });
+
+        let foos = [Foo::A, Foo::B(3), Foo::C { x: -1, y: 2 }];
+        let bars = foos.iter().map(Bar::from);
+        assert!(bars.eq([Bar::X, Bar::Y(3), Bar::Z { x: -1, y: 2 }]));
+
+        let bar = Bar::Z { x: 5, y: 6 };
+        assert_eq!(Foo::from(&bar), Foo::C { x: 5, y: 6 });
+        assert_eq!(bar, Bar::Z { x: 5, y: 6 });
+    }
+
+    #[test]
+    fn inline_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[inline] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(4), Bar::Y(4));
+    }
+
+    #[test]
+    fn must_use_inline_flags() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[must_use] #[inline] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(4), Bar::Y(4));
+    }
+
+    #[test]
+    fn inline_by_ref_flags() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        bijection!(#[by_ref] #[inline] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        test_bijection_eq(Foo::B, Bar::Y);
+        assert_eq!(Bar::from(&Foo::A), Bar::X);
+        assert_eq!(Foo::from(&Bar::Y), Foo::B);
+    }
+
+    #[test]
+    fn doc_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        bijection!(#[doc = "Single doc line"] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B, Bar::Y);
+    }
+
+    #[test]
+    fn doc_flag_multiple() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo(u8);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Bar(u8);
+
+        bijection!(
+            #[doc = "First doc line"]
+            #[doc = ""]
+            #[doc = "Third doc line"]
+            #[inline]
+            Foo, Bar, {
+                Foo(x) => Bar(x),
+            }
+        );
+
+        test_bijection_eq(Foo(1), Bar(1));
+    }
+
+    #[test]
+    fn module_prefix() {
+        mod types {
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Foo {
+                A,
+                B(u8),
+            }
+
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Bar {
+                X,
+                Y(u8),
+            }
+
+            bijection!(
+                #[cfg(all())]
+                pub(crate) mod conv;
+                #[by_ref] Foo, Bar, {
+                    Foo::A => Bar::X,
+                    Foo::B(n) => Bar::Y(n),
+                } test_samples(_foo_bar_roundtrip) = [Foo::A, Foo::B(1)]
+            );
+
+            // Each roundtrip test is in the module of its bijection
+            #[derive(Debug, PartialEq, Clone)]
+            pub struct Meters(pub u32);
+
+            bijection!(mod meters; Meters, u32, {
+                Meters(m) => m,
+            } test_samples(_meters_roundtrip) = [Meters(5)]);
+        }
+
+        // Fails to compile if the module wasn't created
+        #[allow(unused_imports)]
+        use types::conv as _;
+        use types::{Bar, Foo};
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(2), Bar::Y(2));
+        assert_eq!(Bar::from(&Foo::B(3)), Bar::Y(3));
+    }
+
+    #[test]
+    fn block_imports() {
+        mod shapes {
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Shape {
+                Circle(u32),
+                Square { side: u32 },
+                Point,
+            }
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Figure {
+            Round(u32),
+            Boxy(u32),
+            Dot,
+        }
+
+        bijection!(#[by_ref] shapes::Shape, Figure, {
+            use shapes::Shape::*;
+            use Figure::{Boxy, Dot, Round};
+
+            Circle(r) => Round(r),
+            Square { side } => Boxy(side),
+            Point => Dot,
+        });
+
+        bijection!(Figure => u8, {
+            use Figure::*;
+            Round(_) => 0,
+            Boxy(_) => 1,
+            Dot => 2,
+        });
+
+        try_bijection!(u32, shapes::Shape, {
+            use shapes::Shape as S;
+            0 => S::Point,
+        });
+
+        test_bijection_eq(shapes::Shape::Circle(2), Figure::Round(2));
+        test_bijection_eq(shapes::Shape::Square { side: 3 }, Figure::Boxy(3));
+        test_bijection_eq(shapes::Shape::Point, Figure::Dot);
+        assert_eq!(Figure::from(&shapes::Shape::Point), Figure::Dot);
+        assert_eq!(u8::from(Figure::Boxy(7)), 1);
+        assert_eq!(shapes::Shape::try_from(0), Ok(shapes::Shape::Point));
+    }
+
+    #[test]
+    fn automatically_derived() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Level {
+            Low,
+            High,
+        }
+
+        // Attributes on the impls must still be accepted next to the flags adding their own
+        bijection!(#[by_ref] #[eq] #[doc = "Converts Foo and Bar"] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(n) => Bar::Y(n),
+        });
+
+        bijection!(Level, u8, {
+            Level::Low => 0,
+            Level::High => 1,
+        });
+
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        assert_eq!(Bar::from(&Foo::A), Bar::X);
+        assert!(Foo::A == Bar::X);
+        assert_eq!(Level::try_from(1), Ok(Level::High));
+        assert_eq!(Level::try_from(2), Err(2));
+    }
+
+    #[test]
+    fn branch_docs() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C,
+            Other,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z,
+            Unknown,
+        }
+
+        bijection!(#[doc = "Maps foos to bars"] Foo, Bar, {
+            /// first
+            Foo::A => Bar::X,
+            /// second,
+            /// on two lines
+            #[cfg(all())]
+            Foo::B(n) => Bar::Y(n),
+            Foo::C => Bar::Z,
+            /// catch-all
+            Foo::Other => Bar::Unknown; reverse Bar::Unknown => Foo::Other,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(4), Bar::Y(4));
+        test_bijection_eq(Foo::C, Bar::Z);
+        test_bijection_eq(Foo::Other, Bar::Unknown);
+
+        #[derive(Debug, PartialEq)]
+        enum Sign {
+            Negative,
+            Zero,
+        }
+
+        bijection!(Sign, i8, {
+            /// negative
+            Sign::Negative => -1,
+            Sign::Zero => 0,
+        });
+
+        assert_eq!(i8::from(Sign::Negative), -1);
+        assert_eq!(Sign::try_from(0), Ok(Sign::Zero));
+    }
+
+    #[test]
+    fn borrow_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C { name: &'static str },
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z(&'static str),
+        }
+
+        bijection!(#[borrow] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+            Foo::C { name } => Bar::Z(name),
+        });
+
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+
+        let foo = Foo::C { name: "c" };
+        assert_eq!(Bar::from(&foo), Bar::Z("c"));
+        assert_eq!(Bar::from(&Foo::A), Bar::X);
+        assert_eq!(Bar::from(&Foo::B(2)), Bar::Y(2));
+        assert_eq!(foo, Foo::C { name: "c" });
+    }
+
+    #[test]
+    fn borrow_view() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum BarView<'a> {
+            X,
+            Y(&'a u8),
+        }
+
+        bijection!(<'a> &'a Foo => BarView<'a>, {
+            Foo::A => BarView::X,
+            Foo::B(b) => BarView::Y(b),
+        });
+
+        let foo = Foo::B(2);
+        assert_eq!(BarView::from(&foo), BarView::Y(&2));
+        assert_eq!(BarView::from(&Foo::A), BarView::X);
+    }
+
+    #[test]
+    fn warn_lossy_flag() {
+        #[allow(deprecated)]
+        mod lossy {
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Foo {
+                A,
+                Unknown,
+                Reserved,
+            }
+
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Bar {
+                X,
+                Unknown,
+            }
+
+            bijection!(#[warn_lossy] Foo, Bar, {
+                Foo::A => Bar::X,
+                _ => Bar::Unknown; reverse Bar::Unknown => Foo::Unknown,
+            });
+        }
+        use lossy::{Bar, Foo};
+
+        assert_eq!(Bar::from(Foo::A), Bar::X);
+        assert_eq!(Bar::from(Foo::Reserved), Bar::Unknown);
+        assert_eq!(Foo::from(Bar::Unknown), Foo::Unknown);
+    }
+
+    #[test]
+    fn auto_into_forward() {
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            A(u8),
+            B(u8, Option<u8>),
+            C,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Bar {
+            X(u16),
+            Y(u16, Option<u8>),
+            Z,
+        }
+
+        bijection!(#[auto_into] Foo => Bar, {
+            Foo::A(x) => Bar::X(x),
+            Foo::B(x, y) => Bar::Y(x, y),
+            Foo::C => Bar::Z,
+        });
+
+        assert_eq!(Bar::from(Foo::A(3)), Bar::X(3));
+        assert_eq!(Bar::from(Foo::B(255, Some(1))), Bar::Y(255, Some(1)));
+        assert_eq!(Bar::from(Foo::C), Bar::Z);
+    }
+
+    #[test]
+    fn strict_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[strict] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(x) => Bar::Y(x),
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(2), Bar::Y(2));
+    }
+
+    #[test]
+    fn count_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            Other,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Unknown,
+        }
+
+        bijection!(#[count] #[source_non_exhaustive] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(x) => Bar::Y(x),
+            #[cfg(any())]
+            Foo::C => Bar::Z,
+            _ => Bar::Unknown; reverse Bar::Unknown => Foo::Other,
+        });
+
+        // Branches disabled by `#[cfg(...)]` aren't counted
+        assert_eq!(Foo::BIJECTION_ARMS, 3);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Meters(u32);
+
+        bijection!(#[count] Meters => u64, {
+            Meters(0) => 0,
+            Meters(m) => m.into(),
+        });
+
+        assert_eq!(Meters::BIJECTION_ARMS, 2);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        bijection!(#[count] #[table] Op, u8, {
+            Op::Add => 1,
+            Op::Sub => 2,
+        });
+
+        assert_eq!(Op::BIJECTION_ARMS, 2);
+
+        // Counted as written, before `#[reverse_default]` adds its wildcard
+        #[derive(Debug, PartialEq, Clone, Default)]
+        enum Mode {
+            #[default]
+            Off,
+            On,
+        }
+
+        bijection!(#[reverse_default] #[count] Mode, u8, {
+            Mode::Off => 0,
+            Mode::On => 1,
+        });
+
+        assert_eq!(Mode::BIJECTION_ARMS, 2);
+    }
+
+    #[test]
+    fn repr_cast() {
+        #[derive(Debug, PartialEq, Clone)]
+        #[repr(u8)]
+        enum Op {
+            Nop = 0,
+            Add = 0x10,
+            Sub = 0x11,
+            Halt = 0xff,
+        }
+
+        bijection!(#[repr_cast] #[by_ref] #[eq] Op, u8, {
+            Op::Nop => 0,
+            Op::Add => 0x10,
+            Op::Sub => 0x11,
+            #[cfg(any())]
+            Op::Mul => 0x12,
+            Op::Halt => 0xff,
+        });
+
+        for (op, code) in [
+            (Op::Nop, 0),
+            (Op::Add, 0x10),
+            (Op::Sub, 0x11),
+            (Op::Halt, 0xff),
+        ] {
+            assert_eq!(u8::from(&op), code);
+            assert_eq!(u8::from(op.clone()), op.clone() as u8);
+            assert_eq!(Op::try_from(code), Ok(op.clone()));
+            assert!(op == code);
+        }
+        assert_eq!(Op::try_from(0x12), Err(0x12));
+
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[repr(i16)]
+        enum Level {
+            Low = -100,
+            High = 100,
+        }
+
+        // Flags adding a wildcard to the forward group don't affect the checks
+        bijection!(#[source_non_exhaustive] #[repr_cast] Level, i16, {
+            Level::Low => -100,
+            Level::High => 100,
+        });
+
+        assert_eq!(i16::from(Level::Low), -100);
+        assert_eq!(Level::try_from(100i16), Ok(Level::High));
+        assert_eq!(Level::try_from(0i16), Err(0));
+    }
+
+    #[test]
+    fn same_arity() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+            C,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z,
+        }
+
+        try_bijection!(#[same_arity(3)] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+            #[cfg(any())]
+            Foo::D => Bar::W,
+            Foo::C => Bar::Z,
+        });
+
+        assert_eq!(Bar::try_from(Foo::C), Ok(Bar::Z));
+
+        const VARIANTS: usize = 2;
+
+        #[derive(Debug, PartialEq, Clone, Default)]
+        enum Mode {
+            #[default]
+            Off,
+            On,
+        }
+
+        bijection!(#[source_non_exhaustive] #[reverse_default] #[same_arity(VARIANTS)] Mode, u8, {
+            Mode::Off => 0,
+            Mode::On => 1,
+        });
+
+        assert_eq!(Mode::from(7), Mode::Off);
+    }
+
+    #[test]
+    fn literal_suffixes() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Byte(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Word(u16),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Byte(0u8) => Bar::Word(0u16),
+            Foo::Byte(1) => Bar::Word(256),
+            _ => Bar::Word(u16::MAX); reverse _ => Foo::Byte(u8::MAX),
+        });
+
+        test_bijection_eq(Foo::Byte(0), Bar::Word(0));
+        test_bijection_eq(Foo::Byte(1), Bar::Word(256));
+        assert_eq!(Bar::from(Foo::Byte(2)), Bar::Word(u16::MAX));
+    }
+
+    #[test]
+    fn auto_into_both_ways() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        struct Wide(u16);
+
+        impl From<u8> for Wide {
+            fn from(value: u8) -> Self {
+                Wide(value.into())
+            }
+        }
+
+        impl From<Wide> for u8 {
+            fn from(value: Wide) -> Self {
+                value.0 as u8
+            }
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(u8),
+            B { id: u8, flag: bool },
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(Wide),
+            Y { id: Wide, flag: bool },
+        }
+
+        bijection!(#[auto_into] Foo, Bar, {
+            Foo::A(x) => Bar::X(x),
+            Foo::B { id, flag } => Bar::Y { id, flag },
+        });
+
+        test_bijection_eq(Foo::A(3), Bar::X(Wide(3)));
+        test_bijection_eq(
+            Foo::B { id: 7, flag: true },
+            Bar::Y {
+                id: Wide(7),
+                flag: true,
+            },
+        );
+    }
+
+    #[test]
+    fn auto_into_wrapper_fields() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        struct InnerA(u32);
+
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        struct InnerB(u32);
+
+        impl From<InnerA> for InnerB {
+            fn from(value: InnerA) -> Self {
+                InnerB(value.0 * 10)
+            }
+        }
+
+        impl From<InnerB> for InnerA {
+            fn from(value: InnerB) -> Self {
+                InnerA(value.0 / 10)
+            }
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(InnerA),
+            Pair(InnerA, u8),
+            Named { inner: InnerA },
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(InnerB),
+            Pair(u8, InnerB),
+            Named { value: InnerB },
+        }
+
+        bijection!(#[auto_into] #[by_ref] Foo, Bar, {
+            Foo::A(x) => Bar::X(x),
+            Foo::Pair(x, n) => Bar::Pair(n, x),
+            Foo::Named { inner } => Bar::Named { value: inner },
+        });
+
+        test_bijection_eq(Foo::A(InnerA(2)), Bar::X(InnerB(20)));
+        test_bijection_eq(Foo::Pair(InnerA(3), 1), Bar::Pair(1, InnerB(30)));
+        test_bijection_eq(
+            Foo::Named { inner: InnerA(4) },
+            Bar::Named { value: InnerB(40) },
+        );
+        assert_eq!(Foo::from(&Bar::X(InnerB(50))), Foo::A(InnerA(5)));
+    }
+
+    #[test]
+    fn borrowed_first() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            A(String),
+            B(u32),
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Bar {
+            X,
+            Y(u32),
+        }
+
+        bijection!(&Foo, Bar, {
+            Foo::A(_) => Bar::X; reverse Bar::X => Foo::A(String::from("default")),
+            Foo::B(x) => Bar::Y(x),
+        });
+
+        assert_eq!(Bar::from(&Foo::A(String::from("a"))), Bar::X);
+        assert_eq!(Bar::from(&Foo::B(5)), Bar::Y(5));
+        assert_eq!(Foo::from(Bar::X), Foo::A(String::from("default")));
+        assert_eq!(Foo::from(Bar::Y(5)), Foo::B(5));
+    }
+
+    #[test]
+    fn borrowed_second() {
+        #[derive(Debug, PartialEq)]
+        enum Switch {
+            On,
+            Off,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Light {
+            on: bool,
+        }
+
+        bijection!(Switch, &Light, {
+            Switch::On => Light { on: true },
+            Switch::Off => Light { on: false },
+        });
+
+        assert_eq!(Light::from(Switch::On), Light { on: true });
+        assert_eq!(Switch::from(&Light { on: false }), Switch::Off);
+    }
+
+    #[test]
+    fn borrowed_integer() {
+        #[derive(Debug, PartialEq)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        bijection!(&Op, u8, {
+            Op::Add => 0,
+            Op::Sub => 1,
+        });
+
+        assert_eq!(u8::from(&Op::Add), 0);
+        assert_eq!(Op::try_from(1), Ok(Op::Sub));
+        assert_eq!(Op::try_from(2), Err(2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_flag() {
+        extern crate alloc;
+        use alloc::boxed::Box;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[boxed] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        });
+
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        test_bijection_eq(Box::new(Foo::A), Box::new(Bar::X));
+        test_bijection_eq(Box::new(Foo::B(2)), Box::new(Bar::Y(2)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn recursive_types() {
+        extern crate alloc;
+        use alloc::boxed::Box;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Tree {
+            Leaf(i32),
+            Node(Box<Tree>, Box<Tree>),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum OtherTree {
+            Leaf(i32),
+            Node(Box<OtherTree>, Box<OtherTree>),
+        }
+
+        // The children are converted by the very impls being generated
+        bijection!(Tree, OtherTree, {
+            Tree::Leaf(x) => OtherTree::Leaf(x),
+            Tree::Node(l, r) => OtherTree::Node(Box::new((*l).into()), Box::new((*r).into()));
+                reverse OtherTree::Node(l, r) => Tree::Node(Box::new((*l).into()), Box::new((*r).into())),
+        });
+
+        let tree = Tree::Node(
+            Box::new(Tree::Leaf(1)),
+            Box::new(Tree::Node(Box::new(Tree::Leaf(2)), Box::new(Tree::Leaf(3)))),
+        );
+        let other = OtherTree::Node(
+            Box::new(OtherTree::Leaf(1)),
+            Box::new(OtherTree::Node(
+                Box::new(OtherTree::Leaf(2)),
+                Box::new(OtherTree::Leaf(3)),
+            )),
+        );
+        test_bijection_eq(tree.clone(), other.clone());
+
+        // With the boxed impls, the children can be converted like any other field
+        #[derive(Debug, PartialEq, Clone)]
+        enum Mirror {
+            Leaf(i32),
+            Node(Box<Mirror>, Box<Mirror>),
+        }
+
+        bijection!(#[boxed] #[auto_into] Tree, Mirror, {
+            Tree::Leaf(x) => Mirror::Leaf(x),
+            Tree::Node(l, r) => Mirror::Node(l, r),
+        });
+
+        let mirror = Mirror::Node(
+            Box::new(Mirror::Leaf(1)),
+            Box::new(Mirror::Node(
+                Box::new(Mirror::Leaf(2)),
+                Box::new(Mirror::Leaf(3)),
+            )),
+        );
+        test_bijection_eq(tree, mirror);
+    }
+
+    #[test]
+    fn const_flag() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        bijection!(#[const(to_bar, to_foo)] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+        });
+
+        const BARS: [Bar; 2] = [Foo::A.to_bar(), Foo::B.to_bar()];
+        const FOO: Foo = Bar::Y.to_foo();
+
+        test_bijection_eq(Foo::A, Bar::X);
+        assert_eq!(BARS, [Bar::X, Bar::Y]);
+        assert_eq!(FOO, Foo::B);
+    }
+
+    #[test]
+    fn const_flag_literals() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        bijection!(#[const(code)] Op => u8, {
+            Op::Add => b'+',
+            Op::Sub => b'-',
+        });
+
+        const CODES: [u8; 2] = [Op::Add.code(), Op::Sub.code()];
+
+        assert_eq!(CODES, *b"+-");
+        assert_eq!(u8::from(Op::Sub), b'-');
+    }
+
+    #[cfg(feature = "proc")]
+    #[test]
+    fn ext_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[ext] #[eq] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        });
+
+        assert_eq!(Foo::B(3).to_bar(), Bar::Y(3));
+        assert_eq!(Bar::X.to_foo(), Foo::A);
+        assert!(FooExt::to_bar(Foo::A) == Foo::A);
+    }
+
+    #[cfg(feature = "proc")]
+    #[test]
+    fn ext_flag_names() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Shown {
+            Yes,
+            No,
+        }
+
+        mod codes {
+            #[derive(Debug, PartialEq, Clone, Copy)]
+            pub enum HTTPStatus {
+                Ok,
+                NotFound,
+            }
+
+            bijection!(#[ext(pub)] HTTPStatus, u16, {
+                HTTPStatus::Ok => 200,
+                HTTPStatus::NotFound => 404,
+            });
+        }
+
+        bijection!(#[ext] Shown, bool, {
+            Shown::Yes => true,
+            Shown::No => false,
+        });
+
+        use codes::{HTTPStatus, HTTPStatusExt, U16Ext};
+
+        assert_eq!(HTTPStatus::NotFound.to_u16(), 404);
+        assert_eq!(200.try_to_http_status(), Ok(HTTPStatus::Ok));
+        assert_eq!(500.try_to_http_status(), Err(500));
+        assert!(Shown::Yes.to_bool());
+        assert_eq!(false.to_shown(), Shown::No);
+    }
+
+    #[test]
+    fn eq_flag() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(#[eq] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        });
+
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        assert_eq!(Foo::A, Bar::X);
+        assert_eq!(Bar::Y(2), Foo::B(2));
+        assert_ne!(Foo::A, Bar::Y(0));
+        assert_ne!(Foo::B(1), Bar::Y(2));
+        assert_ne!(Bar::X, Foo::B(0));
+    }
+
+    #[test]
+    fn eq_flag_by_ref_integer() {
+        #[derive(Debug, PartialEq)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        bijection!(#[eq] #[by_ref] Op, u8, {
+            Op::Add => 0,
+            Op::Sub => 1,
+        });
+
+        assert_eq!(Op::Add, 0);
+        assert_eq!(1, Op::Sub);
+        assert_ne!(Op::Sub, 0);
+        // No variant for 2, so it equals none of them
+        assert_ne!(2, Op::Add);
+        assert_ne!(2, Op::Sub);
+    }
+
+    #[test]
+    fn generic_single_param() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Wrapper<T>(T);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Other<T>(T);
+
+        bijection!(<T> Wrapper<T>, Other<T>, {
+            Wrapper(x) => Other(x),
+        });
+
+        test_bijection_eq(Wrapper(5), Other(5));
+        test_bijection_eq(Wrapper("five"), Other("five"));
+    }
+
+    #[test]
+    fn generic_multiple_params() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Either<L, R> {
+            Left(L),
+            Right(R),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Flipped<L, R> {
+            Left(R),
+            Right(L),
+        }
+
+        bijection!(#[by_ref] <L: Copy, R: Copy> Either<L, R>, Flipped<L, R>, {
+            Either::Left(l) => Flipped::Right(l),
+            Either::Right(r) => Flipped::Left(r),
+        });
+
+        test_bijection_eq(Either::<u8, char>::Left(1), Flipped::Right(1));
+        test_bijection_eq(Either::<u8, char>::Right('r'), Flipped::Left('r'));
+        assert_eq!(
+            Flipped::from(&Either::<u8, char>::Left(2)),
+            Flipped::Right(2)
+        );
+    }
+
+    #[test]
+    fn turbofish_patterns() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Either<L, R> {
+            Left(L),
+            Right(R),
+            Both { left: L, right: R },
+            Neither,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Flipped<L, R> {
+            Left(R),
+            Right(L),
+            Both(R, L),
+            Neither,
+        }
+
+        // Comma-separated (the fast path)
+        bijection!(<L, R> Either<L, R>, Flipped<L, R>, {
+            Either::<L, R>::Left(l) => Flipped::<L, R>::Right(l),
+            Either::<L, R>::Right(r) => Flipped::<L, R>::Left(r),
+            Either::<L, R>::Both { left, right } => Flipped::<L, R>::Both(right, left),
+            Either::<L, R>::Neither => Flipped::<L, R>::Neither,
+        });
+
+        test_bijection_eq(Either::<u8, char>::Left(1), Flipped::Right(1));
+        test_bijection_eq(Either::<u8, char>::Right('r'), Flipped::Left('r'));
+        test_bijection_eq(
+            Either::Both {
+                left: 2u8,
+                right: 's',
+            },
+            Flipped::Both('s', 2u8),
+        );
+        test_bijection_eq(Either::<u8, char>::Neither, Flipped::Neither);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Code {
+            Zero,
+            Number(u8),
+            Letter(char),
+            Partial(u8),
+            Unknown,
+        }
+
+        // Concrete type arguments, with a literal, a binding subpattern and an explicit reverse
+        bijection!(Either<u8, char>, Code, {
+            Either::<u8, char>::Left(0) => Code::Zero;
+            Either::<u8, char>::Left(n @ 1..) => Code::Number(n);
+            Either::<u8, char>::Right(c) => Code::Letter(c);
+            Either::<u8, char>::Both { left, .. } => Code::Partial(left);
+                reverse Code::Partial(left) => Either::<u8, char>::Both { left, right: '?' };
+            Either::<u8, char>::Neither => Code::Unknown;
+        });
+
+        test_bijection_eq(Either::<u8, char>::Left(0), Code::Zero);
+        test_bijection_eq(Either::<u8, char>::Left(3), Code::Number(3));
+        test_bijection_eq(Either::<u8, char>::Right('c'), Code::Letter('c'));
+        test_bijection_eq(Either::<u8, char>::Neither, Code::Unknown);
+        assert_eq!(
+            Code::from(Either::Both {
+                left: 4,
+                right: 'x'
+            }),
+            Code::Partial(4)
+        );
+        assert_eq!(
+            Either::from(Code::Partial(4)),
+            Either::Both {
+                left: 4,
+                right: '?'
+            }
+        );
+    }
+
+    #[test]
+    fn generic_where_clause() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Single<T>(T);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Double<T>(T, T);
+
+        bijection!(<T> Single<T>, Double<T> where T: Clone, {
+            Single(x) => Double(x.clone(), x); reverse Double(x, _) => Single(x),
+        });
+
+        assert_eq!(Double::from(Single(3)), Double(3, 3));
+        assert_eq!(Single::from(Double(1, 2)), Single(1));
+    }
+
+    #[test]
+    fn generic_where_clause_multiple() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo<T, U>(T, U);
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar<T, U> {
+            Pair(T, U),
+        }
+
+        bijection!(<T, U> Foo<T, U>, Bar<T, U> where T: Clone + Debug, U: Copy, {
+            Foo(t, u) => Bar::Pair(t, u),
+        });
+
+        test_bijection_eq(Foo('a', 2), Bar::Pair('a', 2));
+    }
+
+    #[test]
+    fn generic_lifetimes() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum InputView<'a> {
+            Empty,
+            Text(&'a str),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum ParsedView<'a> {
+            Nothing,
+            Word(&'a str),
+        }
+
+        bijection!(<'a> InputView<'a>, ParsedView<'a>, {
+            InputView::Empty => ParsedView::Nothing,
+            InputView::Text(text) => ParsedView::Word(text),
+        });
+
+        let text = [b'o', b'k'];
+        let text = core::str::from_utf8(&text).unwrap();
+        test_bijection_eq(InputView::Text(text), ParsedView::Word(text));
+        test_bijection_eq(InputView::Empty, ParsedView::Nothing);
+    }
+
+    #[test]
+    fn generic_lifetimes_and_types() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo<'a, T>(&'a T);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Bar<'a, T>(&'a T);
+
+        bijection!(<'a, T: 'a> Foo<'a, T>, Bar<'a, T>, {
+            Foo(x) => Bar(x),
+        });
+
+        test_bijection_eq(Foo(&1), Bar(&1));
+    }
+
+    // The generated roundtrip tests must be items of a module to be run
+    mod roundtrip_tests {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(b) => Bar::Y(b),
+        } test_samples(foo_bar_roundtrip) = [Foo::A, Foo::B(0), Foo::B(u8::MAX)]);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Wrapper<T>(T);
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Other<T>(T);
+
+        // A second roundtrip test in the same scope, with its own name
+        bijection!(<T> Wrapper<T>, Other<T>, {
+            Wrapper(x) => Other(x),
+        } test_samples(generic_roundtrip) = [Wrapper(1), Wrapper(-1)]);
+
+        #[test]
+        fn roundtrip_tests_are_generated() {
+            foo_bar_roundtrip();
+            generic_roundtrip();
+        }
+    }
+
+    #[test]
+    fn similar_right_hand_sides() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(u8),
+            B(u8),
+            C,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(u8, bool),
+            Y,
+        }
+
+        // Textually close, but none of these are duplicates
+        #[deny(unreachable_patterns)]
+        {
+            bijection!(Foo, Bar, {
+                Foo::A(x) => Bar::X(x, true),
+                Foo::B(x) => Bar::X(x, false),
+                Foo::C => Bar::Y,
+            });
+        }
+
+        test_bijection_eq(Foo::A(1), Bar::X(1, true));
+        test_bijection_eq(Foo::B(2), Bar::X(2, false));
+        test_bijection_eq(Foo::C, Bar::Y);
+    }
+
+    #[test]
+    fn integer_codes() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Op {
+            Add,
+            Sub,
+            Nop,
+        }
+
+        bijection!(Op, u8, {
+            Op::Add => 0,
+            Op::Sub => 1,
+            Op::Nop => 0xFF,
+        });
+
+        assert_eq!(u8::from(Op::Add), 0);
+        assert_eq!(u8::from(Op::Sub), 1);
+        assert_eq!(u8::from(Op::Nop), 255);
+
+        assert_eq!(Op::try_from(0), Ok(Op::Add));
+        assert_eq!(Op::try_from(1), Ok(Op::Sub));
+        assert_eq!(Op::try_from(255), Ok(Op::Nop));
+        assert_eq!(Op::try_from(2), Err(2));
+    }
+
+    #[test]
+    fn integer_signed_with_data() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Level {
+            Low,
+            High,
+        }
+
+        bijection!(#[by_ref] Level, i64, {
+            Level::Low => -1,
+            Level::High => 1,
+        });
+
+        assert_eq!(i64::from(&Level::Low), -1);
+        assert_eq!(Level::try_from(1_i64), Ok(Level::High));
+        assert_eq!(Level::try_from(&-1_i64), Ok(Level::Low));
+        assert_eq!(Level::try_from(0_i64), Err(0));
+    }
+
+    #[test]
+    fn integer_binding() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        struct Wrapper(u8);
+
+        bijection!(Wrapper, u8, { Wrapper(x) => x });
+
+        test_bijection_eq(Wrapper(3), 3u8);
+        assert_eq!(Wrapper::from(255u8), Wrapper(255));
+    }
+
+    #[test]
+    fn integer_custom_error() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum OpError {
+            Unknown(u16),
+        }
+
+        impl From<u16> for OpError {
+            fn from(value: u16) -> Self {
+                OpError::Unknown(value)
+            }
+        }
+
+        bijection!(Op, u16, error = OpError, {
+            Op::Add => 10,
+            Op::Sub => 20,
+        });
+
+        assert_eq!(u16::from(Op::Add), 10);
+        assert_eq!(Op::try_from(20), Ok(Op::Sub));
+        assert_eq!(Op::try_from(30), Err(OpError::Unknown(30)));
+    }
+
+    #[test]
+    fn partial_type_consts() {
+        mod flags {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct Flags(pub u8);
+
+            impl Flags {
+                pub const READ: Flags = Flags(0b001);
+                pub const WRITE: Flags = Flags(0b010);
+                pub const EXEC: Flags = Flags(0b100);
+            }
+        }
+
+        use flags::Flags;
+
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Flag {
+            Read,
+            Write,
+            Exec,
+        }
+
+        // A path as the second type
+        bijection!(#[by_ref] Flag, flags::Flags, error = flags::Flags, {
+            Flag::Read => Flags::READ,
+            Flag::Write => Flags::WRITE,
+            Flag::Exec => Flags::EXEC,
+        });
+
+        #[derive(Debug, PartialEq, Clone, Copy, Eq)]
+        struct Mode(u8);
+
+        impl Mode {
+            const OFF: Mode = Mode(0);
+            const ON: Mode = Mode(1);
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Switch {
+            Off,
+            On,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct UnknownMode(u8);
+
+        impl From<Mode> for UnknownMode {
+            fn from(value: Mode) -> Self {
+                UnknownMode(value.0)
+            }
+        }
+
+        // A single identifier as the second type
+        bijection!(Switch, Mode, error = UnknownMode, {
+            Switch::Off => Mode::OFF,
+            Switch::On => Mode::ON,
+        });
+
+        assert_eq!(Flags::from(Flag::Write), Flags(0b010));
+        assert_eq!(Flags::from(&Flag::Exec), Flags::EXEC);
+        assert_eq!(Flag::try_from(Flags::READ), Ok(Flag::Read));
+        assert_eq!(Flag::try_from(&Flags(0b100)), Ok(Flag::Exec));
+        assert_eq!(Flag::try_from(Flags(0b011)), Err(Flags(0b011)));
+        assert_eq!(Flag::try_from(Flags(0)), Err(Flags(0)));
+
+        assert_eq!(Mode::from(Switch::On), Mode::ON);
+        assert_eq!(Switch::try_from(Mode(0)), Ok(Switch::Off));
+        assert_eq!(Switch::try_from(Mode(2)), Err(UnknownMode(2)));
+    }
+
+    #[test]
+    fn table_lookup() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Level {
+            Trace,
+            Debug,
+            Info,
+            Warn,
+            Error,
+            Off,
+        }
+
+        bijection!(#[table] #[by_ref] Level, i16, {
+            Level::Info => 20,
+            Level::Off => -1,
+            Level::Trace => 0,
+            Level::Error => 40,
+            Level::Debug => 10,
+            Level::Warn => 30,
+        });
+
+        assert!(
+            Level::LOOKUP
+                .iter()
+                .map(|(key, _)| *key)
+                .eq([-1, 0, 10, 20, 30, 40])
+        );
+
+        for level in [
+            Level::Trace,
+            Level::Debug,
+            Level::Info,
+            Level::Warn,
+            Level::Error,
+            Level::Off,
+        ] {
+            assert_eq!(Level::try_from(i16::from(level)), Ok(level));
+            assert_eq!(Level::try_from(&i16::from(&level)), Ok(level));
+        }
+        for unknown in [i16::MIN, -2, 5, 15, 41, i16::MAX] {
+            assert_eq!(Level::try_from(unknown), Err(unknown));
+        }
+    }
+
+    #[test]
+    fn table_lookup_char() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Token {
+            Plus,
+            Minus,
+            Star,
+        }
+
+        bijection!(#[table] Token, char, {
+            Token::Star => '*',
+            Token::Plus => '+',
+            Token::Minus => '-',
+        });
+
+        assert_eq!(
+            Token::LOOKUP,
+            &[('*', Token::Star), ('+', Token::Plus), ('-', Token::Minus)]
+        );
+        assert_eq!(Token::try_from('-'), Ok(Token::Minus));
+        assert_eq!(Token::try_from('/'), Err('/'));
+        assert_eq!(char::from(Token::Plus), '+');
+    }
+
+    #[test]
+    fn str_keys() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Cmd {
+            Go,
+            Stop,
+        }
+
+        bijection!(&'static str, Cmd, {
+            "go" => Cmd::Go,
+            "stop" => Cmd::Stop,
+        });
+
+        assert_eq!(Cmd::try_from("go"), Ok(Cmd::Go));
+        assert_eq!(Cmd::try_from("stop"), Ok(Cmd::Stop));
+        assert_eq!(<&str>::from(Cmd::Stop), "stop");
+
+        // Any string can be looked up, and unknown ones are returned
+        let owned = String::from("jump");
+        assert_eq!(Cmd::try_from(owned.as_str()), Err("jump"));
+    }
+
+    #[test]
+    fn str_keys_second() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq)]
+        enum Color {
+            Red,
+            Green,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct UnknownColor(String);
+
+        impl From<&str> for UnknownColor {
+            fn from(value: &str) -> Self {
+                UnknownColor(value.into())
+            }
+        }
+
+        bijection!(Color, &str, error = UnknownColor, {
+            Color::Red => "red",
+            Color::Green => "green",
+        });
+
+        let name: &'static str = Color::Green.into();
+        assert_eq!(name, "green");
+        assert_eq!(Color::try_from("red"), Ok(Color::Red));
+        assert_eq!(Color::try_from("blue"), Err(UnknownColor("blue".into())));
+    }
+
+    #[test]
+    fn long_tuple_variants() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Tri(u8, u16, u32),
+            Quad(u8, u8, u8, u8),
+            Five(u8, i8, u16, i16, bool),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Tri(u32, u16, u8),
+            Quad(u8, u8, u8, u8),
+            Five(bool, i16, u16, i8, u8),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Tri(a, b, c) => Bar::Tri(c, b, a),
+            Foo::Quad(a, b, c, d) => Bar::Quad(b, a, d, c),
+            Foo::Five(a, b, c, d, e) => Bar::Five(e, d, c, b, a),
+        });
+
+        test_bijection_eq(Foo::Tri(1, 2, 3), Bar::Tri(3, 2, 1));
+        test_bijection_eq(Foo::Quad(1, 2, 3, 4), Bar::Quad(2, 1, 4, 3));
+        test_bijection_eq(Foo::Five(1, -2, 3, -4, true), Bar::Five(true, -4, 3, -2, 1));
+
+        // The same arities in order, with literals (which skip the fast path)
+        #[derive(Debug, PartialEq, Clone)]
+        enum Baz {
+            Tri(u8, u16, u32),
+            Quad(u8, u8, u8, u8),
+            Five(u8, i8, u16, i16, bool),
+        }
+
+        bijection!(Bar, Baz, {
+            Bar::Tri(0, b, a) => Baz::Tri(a, b, 0),
+            Bar::Tri(c, b, a) => Baz::Tri(a, b, c),
+            Bar::Quad(a, b, c, d) => Baz::Quad(a, b, c, d),
+            Bar::Five(e, d, c, b, a) => Baz::Five(a, b, c, d, e),
+        });
+
+        test_bijection_eq(Bar::Tri(0, 2, 1), Baz::Tri(1, 2, 0));
+        test_bijection_eq(Bar::Tri(3, 2, 1), Baz::Tri(1, 2, 3));
+        test_bijection_eq(Bar::Quad(1, 2, 3, 4), Baz::Quad(1, 2, 3, 4));
+        test_bijection_eq(Bar::Five(true, -4, 3, -2, 1), Baz::Five(1, -2, 3, -4, true));
+    }
+
+    #[test]
+    fn renamed_fields() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo {
+            a: u8,
+            b: i32,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Bar {
+            x: u8,
+            y: i32,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo { a, b } => Bar { x: a, y: b },
+        });
+
+        test_bijection_eq(Foo { a: 1, b: -2 }, Bar { x: 1, y: -2 });
+
+        // Renamed on both sides, swapped, and nested in a tuple variant
+        #[derive(Debug, PartialEq, Clone)]
+        enum Baz {
+            Point { left: u8, right: i32 },
+            Wrapped(Foo),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Qux {
+            Point { second: i32, first: u8 },
+            Wrapped(Bar),
+        }
+
+        bijection!(Baz, Qux, {
+            Baz::Point { left: l, right: r } => Qux::Point { second: r, first: l },
+            Baz::Wrapped(Foo { a: p, b: q }) => Qux::Wrapped(Bar { x: p, y: q }),
+        });
+
+        test_bijection_eq(
+            Baz::Point { left: 3, right: 4 },
+            Qux::Point {
+                first: 3,
+                second: 4,
+            },
+        );
+        test_bijection_eq(
+            Baz::Wrapped(Foo { a: 5, b: 6 }),
+            Qux::Wrapped(Bar { x: 5, y: 6 }),
+        );
+
+        // The same with converted fields, where the renamed ones aren't read as shorthands
+        #[derive(Debug, PartialEq, Clone)]
+        struct Wide {
+            first: u16,
+            second: i64,
+        }
+
+        bijection!(#[auto_into] Bar => Wide, {
+            Bar { x, y: b } => Wide { first: x, second: b },
+        });
+
+        assert_eq!(
+            Wide::from(Bar { x: 7, y: 8 }),
+            Wide {
+                first: 7,
+                second: 8
+            }
+        );
+    }
+
+    #[test]
+    fn bare_bool() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Switch {
+            On,
+            Off,
+        }
+
+        bijection!(bool, Switch, {
+            true => Switch::On,
+            false => Switch::Off,
+        });
+
+        test_bijection_eq(true, Switch::On);
+        test_bijection_eq(false, Switch::Off);
+
+        // `bool` is matched exhaustively on the second side as well, unlike integers
+        #[derive(Debug, PartialEq, Clone)]
+        enum Visibility {
+            Shown,
+            Hidden,
+        }
+
+        bijection!(Visibility, bool, {
+            Visibility::Shown => true,
+            Visibility::Hidden => false,
+        });
+
+        test_bijection_eq(Visibility::Shown, true);
+        test_bijection_eq(Visibility::Hidden, false);
+    }
+
+    #[test]
+    fn negative_literals() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Temp(i32),
+            Level(f64),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Below,
+            Lowest,
+            Half,
+            Other(i32),
+            Level(f64),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Temp(-1) => Bar::Below,
+            Foo::Temp(i32::MIN) => Bar::Lowest,
+            Foo::Level(-1.5) => Bar::Half,
+            Foo::Temp(t) => Bar::Other(t),
+            Foo::Level(l) => Bar::Level(l),
+        });
+
+        test_bijection_eq(Foo::Temp(-1), Bar::Below);
+        test_bijection_eq(Foo::Temp(i32::MIN), Bar::Lowest);
+        test_bijection_eq(Foo::Level(-1.5), Bar::Half);
+        test_bijection_eq(Foo::Temp(-2), Bar::Other(-2));
+        test_bijection_eq(Foo::Level(-2.5), Bar::Level(-2.5));
+
+        // Negative literals on the other side, and as the whole pattern
+        #[derive(Debug, PartialEq)]
+        enum Sign {
+            Negative,
+            Zero,
+        }
+
+        bijection!(Sign, i8, {
+            Sign::Negative => -1,
+            Sign::Zero => 0,
+        });
+
+        assert_eq!(i8::from(Sign::Negative), -1);
+        assert_eq!(Sign::try_from(-1), Ok(Sign::Negative));
+        assert_eq!(Sign::try_from(-2), Err(-2));
+
+        #[derive(Debug, PartialEq)]
+        enum Scale {
+            Down,
+            Up(f32),
+        }
+
+        try_bijection!(Scale, f32, {
+            Scale::Down => -1.5,
+            Scale::Up(s) => s,
+        });
+
+        assert_eq!(f32::try_from(Scale::Down), Ok(-1.5));
+        assert_eq!(Scale::try_from(-1.5), Ok(Scale::Down));
+        assert_eq!(Scale::try_from(2.0), Ok(Scale::Up(2.0)));
+    }
+
+    #[test]
+    fn const_patterns() {
+        const MIN_CODE: u16 = 100;
+
+        struct Limits;
+
+        impl Limits {
+            const MAX: u16 = 999;
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Code(u16),
+            Lowest,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Min,
+            Max,
+            Other(u16),
+        }
+
+        // Consts are read as paths in both directions, on either side
+        bijection!(Foo, Bar, {
+            Foo::Code(MIN_CODE) => Bar::Min,
+            Foo::Code(Limits::MAX) => Bar::Max,
+            Foo::Lowest => Bar::Other(MIN_CODE),
+            Foo::Code(code) => Bar::Other(code),
+        });
+
+        test_bijection_eq(Foo::Code(100), Bar::Min);
+        test_bijection_eq(Foo::Code(999), Bar::Max);
+        test_bijection_eq(Foo::Lowest, Bar::Other(100));
+        test_bijection_eq(Foo::Code(5), Bar::Other(5));
+    }
+
+    #[test]
+    fn char_tokens() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Token {
+            Plus,
+            Minus,
+            Star,
+            Dot,
+        }
+
+        bijection!(Token, char, {
+            Token::Plus => '+',
+            Token::Minus => '-',
+            Token::Star => '*',
+            Token::Dot => '.',
+        });
+
+        assert_eq!(char::from(Token::Star), '*');
+        assert_eq!(Token::try_from('+'), Ok(Token::Plus));
+        assert_eq!(Token::try_from('.'), Ok(Token::Dot));
+        assert_eq!(Token::try_from('/'), Err('/'));
+    }
+
+    #[test]
+    fn char_tokens_error() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Token {
+            Open,
+            Close,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct UnknownChar(char);
+
+        impl From<char> for UnknownChar {
+            fn from(value: char) -> Self {
+                UnknownChar(value)
+            }
+        }
+
+        bijection!(Token, char, error = UnknownChar, {
+            Token::Open => '(',
+            Token::Close => ')',
+        });
+
+        assert_eq!(char::from(Token::Close), ')');
+        assert_eq!(Token::try_from('('), Ok(Token::Open));
+        assert_eq!(Token::try_from('x'), Err(UnknownChar('x')));
+    }
+
+    #[test]
+    fn source_non_exhaustive() {
+        use core::sync::atomic::Ordering;
+
+        // `Ordering` is `#[non_exhaustive]`, so matching it in this crate needs a wildcard
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Order {
+            Relaxed,
+            Release,
+            Acquire,
+            AcqRel,
+            SeqCst,
+        }
+
+        bijection!(#[source_non_exhaustive] #[by_ref] Ordering, Order, {
+            Ordering::Relaxed => Order::Relaxed,
+            Ordering::Release => Order::Release,
+            Ordering::Acquire => Order::Acquire,
+            Ordering::AcqRel => Order::AcqRel,
+            Ordering::SeqCst => Order::SeqCst,
+        });
+
+        assert_eq!(Order::from(Ordering::Relaxed), Order::Relaxed);
+        assert_eq!(Order::from(&Ordering::Acquire), Order::Acquire);
+        assert_eq!(Ordering::from(Order::Release), Ordering::Release);
+        assert_eq!(Ordering::from(&Order::SeqCst), Ordering::SeqCst);
+    }
+
+    #[test]
+    #[should_panic(expected = "No bijection branch for this Foo value (converting into Bar)")]
+    fn source_non_exhaustive_panic() {
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Bar {
+            X,
+        }
+
+        // The wildcard also catches variants left out on purpose, which makes it reachable here
+        bijection!(#[source_non_exhaustive] Foo, Bar, {
+            Foo::A => Bar::X,
+        });
+
+        assert_eq!(Bar::from(Foo::A), Bar::X);
+        let _ = Bar::from(Foo::B);
+    }
+
+    #[test]
+    #[deny(unreachable_patterns)]
+    fn allow_unreachable() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Foo(u8);
+        #[derive(Debug, PartialEq, Clone)]
+        struct Bar(u8);
+
+        bijection!(#[allow_unreachable] Foo, Bar, {
+            Foo(x) => Bar(x),
+            Foo(0) => Bar(0); reverse Bar(0) => Foo(0),
+        });
+
+        test_bijection_eq(Foo(0), Bar(0));
+        test_bijection_eq(Foo(7), Bar(7));
+    }
+
+    #[test]
+    fn by_mut() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Clone, Default)]
+        enum Foo {
+            #[default]
+            Empty,
+            Name(String),
+        }
+
+        #[derive(Debug, PartialEq, Clone, Default)]
+        struct Bar(Option<String>);
+
+        bijection!(#[by_mut] #[eq] Foo, Bar, {
+            Foo::Empty => Bar(None),
+            Foo::Name(s) => Bar(Some(s)),
+        });
+
+        let mut foo = Foo::Name(String::from("taken"));
+        assert_eq!(Bar::from(&mut foo), Bar(Some(String::from("taken"))));
+        assert_eq!(foo, Foo::Empty);
+
+        let mut bar = Bar(Some(String::from("swapped")));
+        assert_eq!(Foo::from(&mut bar), Foo::Name(String::from("swapped")));
+        assert_eq!(bar, Bar(None));
+        assert_eq!(Foo::from(&mut bar), Foo::Empty);
+
+        test_bijection_eq(Foo::Name(String::from("x")), Bar(Some(String::from("x"))));
+    }
+
+    #[test]
+    fn reverse_default() {
+        #[derive(Debug, PartialEq, Clone, Default)]
+        enum Foo {
+            #[default]
+            Unknown,
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+            Z,
+            Unmapped,
+        }
+
+        bijection!(#[reverse_default] Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(n) => Bar::Y(n),
+            Foo::Unknown => Bar::Z,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        assert_eq!(Foo::from(Bar::Unmapped), Foo::Unknown);
+
+        #[derive(Debug, PartialEq, Clone, Default)]
+        enum Level {
+            #[default]
+            Off,
+            Low,
+            High,
+        }
+
+        bijection!(#[reverse_default] #[by_ref] Level, u8, {
+            Level::Off => 0,
+            Level::Low => 1,
+            Level::High => 2,
+        });
+
+        assert_eq!(Level::from(2), Level::High);
+        assert_eq!(Level::from(&1), Level::Low);
+        // Unmapped values fall back to the default
+        assert_eq!(Level::from(7), Level::Off);
+        assert_eq!(u8::from(Level::Low), 1);
+    }
+
+    #[test]
+    fn newtype_inner_f64() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Meters(f64);
+
+        bijection!(Meters, f64, {
+            Meters(m) => m,
+        });
+
+        test_bijection_eq(Meters(1.5), 1.5);
+        test_bijection_eq(Meters(-0.25), -0.25);
+    }
+
+    #[test]
+    fn newtype_inner_string() {
+        extern crate alloc;
+        use alloc::string::String;
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Name(String);
+
+        bijection!(Name, String, {
+            Name(name) => name,
+        });
+
+        // And with the inner type first
+        #[derive(Debug, PartialEq, Clone)]
+        struct Label(String);
+
+        bijection!(String, Label, {
+            label => Label(label),
+        });
+
+        test_bijection_eq(Name(String::from("abc")), String::from("abc"));
+        test_bijection_eq(String::from("xyz"), Label(String::from("xyz")));
+    }
+
+    #[test]
+    fn rest_pattern_with_reverse() {
+        #[derive(Debug, PartialEq, Clone, Default)]
+        struct Foo {
+            x: u8,
+            y: u8,
+            z: u8,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(u8),
+            Tail(u8),
+        }
+
+        bijection!(Foo, Bar, {
+            Foo { x: 0, z, .. } => Bar::Tail(z); reverse Bar::Tail(z) => Foo { z, ..Default::default() },
+            Foo { x, .. } => Bar::X(x); reverse Bar::X(x) => Foo { x, y: 1, z: 2 },
+        });
+
+        assert_eq!(Bar::from(Foo { x: 0, y: 5, z: 3 }), Bar::Tail(3));
+        assert_eq!(Bar::from(Foo { x: 4, y: 5, z: 6 }), Bar::X(4));
+        test_bijection_eq(Foo { x: 0, y: 0, z: 3 }, Bar::Tail(3));
+        test_bijection_eq(Foo { x: 4, y: 1, z: 2 }, Bar::X(4));
+    }
+
+    #[test]
+    fn discarded_field_default() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            Known(u8),
+            Unknown(u32, bool),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            Known(u8),
+            Unknown,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::Known(n) => Bar::Known(n),
+            Foo::Unknown(_, _) => Bar::Unknown; reverse Bar::Unknown => Foo::Unknown(Default::default(), true),
+        });
+
+        assert_eq!(Bar::from(Foo::Unknown(404, false)), Bar::Unknown);
+        test_bijection_eq(Foo::Unknown(0, true), Bar::Unknown);
+        test_bijection_eq(Foo::Known(1), Bar::Known(1));
+    }
+
+    #[test]
+    fn unit_endpoint() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Unit {
+            Only,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Marker;
+
+        bijection!(Unit, (), {
+            Unit::Only => (),
+        });
+
+        bijection!((), Marker, {
+            () => Marker,
+        });
+
+        test_bijection_eq(Unit::Only, ());
+        test_bijection_eq((), Marker);
+    }
+
+    #[test]
+    fn try_default_error() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A(u8),
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X(u8),
+            Y,
+        }
+
+        try_bijection!(Foo, Bar, {
+            Foo::A(x) => Bar::X(x),
+        });
+
+        assert_eq!(Bar::try_from(Foo::A(1)), Ok(Bar::X(1)));
+        assert_eq!(Foo::try_from(Bar::X(2)), Ok(Foo::A(2)));
+        assert_eq!(Bar::try_from(Foo::B), Err(BijectionError { value: Foo::B }));
+        assert_eq!(
+            Foo::try_from(Bar::Y).map_err(BijectionError::into_value),
+            Err(Bar::Y)
+        );
+    }
+
+    #[test]
+    fn one_hot() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Flags {
+            red: bool,
+            green: bool,
+            blue: bool,
+        }
+
+        // Only three of the eight flag combinations are valid, so the reverse is fallible
+        try_bijection!(Color, Flags, {
+            Color::Red => Flags { red: true, green: false, blue: false },
+            Color::Green => Flags { red: false, green: true, blue: false },
+            Color::Blue => Flags { red: false, green: false, blue: true },
+        });
+
+        for color in [Color::Red, Color::Green, Color::Blue] {
+            let flags = Flags::try_from(color.clone()).unwrap();
+            assert_eq!(Color::try_from(flags), Ok(color));
+        }
+        assert_eq!(
+            Flags::try_from(Color::Green),
+            Ok(Flags {
+                red: false,
+                green: true,
+                blue: false
+            })
+        );
+
+        let none = Flags {
+            red: false,
+            green: false,
+            blue: false,
+        };
+        assert_eq!(
+            Color::try_from(none.clone()),
+            Err(BijectionError { value: none })
+        );
+        let two = Flags {
+            red: true,
+            green: false,
+            blue: true,
+        };
+        assert_eq!(
+            Color::try_from(two.clone()),
+            Err(BijectionError { value: two })
+        );
+    }
+
+    #[test]
+    fn fallback_delegation() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Foo {
+            A,
+            B,
+            Custom(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Bar {
+            X,
+            Y,
+            Code(u8),
+        }
+
+        #[derive(Debug, PartialEq)]
+        enum Error {
+            Foo(Foo),
+            Bar(Bar),
+            TooLarge(u8),
+        }
+
+        impl From<Foo> for Error {
+            fn from(foo: Foo) -> Self {
+                Error::Foo(foo)
+            }
+        }
+
+        impl From<Bar> for Error {
+            fn from(bar: Bar) -> Self {
+                Error::Bar(bar)
+            }
+        }
+
+        fn foo_to_bar(foo: Foo) -> Result<Bar, Error> {
+            match foo {
+                Foo::Custom(n) if n < 100 => Ok(Bar::Code(n)),
+                Foo::Custom(n) => Err(Error::TooLarge(n)),
+                other => Err(Error::Foo(other)),
+            }
+        }
+
+        fn bar_to_foo(bar: Bar) -> Result<Foo, Error> {
+            match bar {
+                Bar::Code(n) => Ok(Foo::Custom(n)),
+                other => Err(Error::Bar(other)),
+            }
+        }
+
+        try_bijection!(#[fallback(foo_to_bar, bar_to_foo)] #[by_ref] Foo, Bar, Error, {
+            Foo::A => Bar::X,
+        });
+
+        assert_eq!(Bar::try_from(Foo::A), Ok(Bar::X));
+        assert_eq!(Foo::try_from(Bar::X), Ok(Foo::A));
+        assert_eq!(Bar::try_from(Foo::Custom(7)), Ok(Bar::Code(7)));
+        assert_eq!(Bar::try_from(&Foo::Custom(8)), Ok(Bar::Code(8)));
+        assert_eq!(Foo::try_from(Bar::Code(7)), Ok(Foo::Custom(7)));
+        assert_eq!(Bar::try_from(Foo::Custom(200)), Err(Error::TooLarge(200)));
+        assert_eq!(Bar::try_from(Foo::B), Err(Error::Foo(Foo::B)));
+        assert_eq!(Foo::try_from(Bar::Y), Err(Error::Bar(Bar::Y)));
+
+        // Only the fallible reverse of an integer bijection
+        #[derive(Debug, PartialEq)]
+        enum Op {
+            Nop,
+            Raw(u8),
+        }
+
+        fn raw_op(n: u8) -> Result<Op, u8> {
+            if n >= 0x80 { Ok(Op::Raw(n)) } else { Err(n) }
+        }
+
+        bijection!(#[fallback(_, raw_op)] Op, u8, {
+            Op::Nop => 0,
+            Op::Raw(n) => n; reverse 0x80 => Op::Raw(0x80),
+        });
+
+        assert_eq!(Op::try_from(0), Ok(Op::Nop));
+        assert_eq!(Op::try_from(0x90), Ok(Op::Raw(0x90)));
+        assert_eq!(Op::try_from(0x10), Err(0x10));
+    }
+
+    #[test]
+    fn bijection_error_display() {
+        extern crate alloc;
+        let error: &dyn core::error::Error = &BijectionError { value: (1, "two") };
+        assert_eq!(
+            alloc::format!("{error}"),
+            "no bijection branch for (1, \"two\")"
+        );
+    }
+
+    #[test]
+    fn try_named_error() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Unmapped;
+
+        impl From<Foo> for Unmapped {
+            fn from(_: Foo) -> Self {
+                Unmapped
+            }
+        }
+
+        impl From<Bar> for Unmapped {
+            fn from(_: Bar) -> Self {
+                Unmapped
+            }
+        }
+
+        try_bijection!(Foo, Bar, error = Unmapped, {
+            Foo::A => Bar::X,
+        });
+
+        assert_eq!(Bar::try_from(Foo::A), Ok(Bar::X));
+        assert_eq!(Bar::try_from(Foo::B), Err(Unmapped));
+        assert_eq!(Foo::try_from(Bar::Y), Err(Unmapped));
+    }
+
+    #[test]
+    fn try_total_shared_error() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct DomainError;
+
+        impl From<Foo> for DomainError {
+            fn from(_: Foo) -> Self {
+                DomainError
+            }
+        }
+
+        impl From<Bar> for DomainError {
+            fn from(_: Bar) -> Self {
+                DomainError
+            }
+        }
+
+        // Every value is covered, the impls just share the error type with other conversions
+        try_bijection!(Foo, Bar, error = DomainError, {
+            Foo::A => Bar::X,
+            Foo::B(n) => Bar::Y(n),
+        });
+
+        let to_bar: fn(Foo) -> Result<Bar, DomainError> = Bar::try_from;
+        assert_eq!(to_bar(Foo::A), Ok(Bar::X));
+        assert_eq!(to_bar(Foo::B(1)), Ok(Bar::Y(1)));
+        assert_eq!(Foo::try_from(Bar::Y(2)), Ok(Foo::B(2)));
+    }
+
+    #[test]
+    fn wildcard_one_direction() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B,
+            Other,
+            Legacy(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y,
+            Other,
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B => Bar::Y,
+            _ => Bar::Other; reverse Bar::Other => Foo::Other,
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B, Bar::Y);
+        test_bijection_eq(Foo::Other, Bar::Other);
+
+        // Only the forward direction collapses the rest
+        assert_eq!(Bar::from(Foo::Legacy(3)), Bar::Other);
+        assert_eq!(Foo::from(Bar::Other), Foo::Other);
+    }
 
-    // Ex: bijection!(Foo { Foo::A => Bar::X })
-    // Note: Slightly unhelpful compiler error message (will complain about `=>` in blocks)
-    ($first_ty:ty $bij:block) => {
-        {
-            let _: $first_ty;
-            compile_error!("Bijection declaration block must be separated with a comma");
+    #[test]
+    fn methods_both_types() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
         }
-    };
 
-    // Ex: bijection!({ Foo::A => Bar::X })
-    // Catches anything contained in curly braces without the leading types
-    ({$($bij:tt)*}) => {
-        compile_error!("Missing types before declaration block");
-    };
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
 
-    // Fallback, catches everything else
-    ($($unknown:tt)*) => {
-        compile_error!("Expected: TypeA, TypeB, { /* bijection patterns */ }");
-    };
-}
+        bijection_methods!(
+            Foo { into_bar, from_bar },
+            Bar { pub into_foo, pub from_foo },
+            {
+                Foo::A => Bar::X,
+                Foo::B(b) => Bar::Y(b),
+            }
+        );
 
-#[cfg(test)]
-mod tests {
-    use core::fmt::Debug;
+        assert_eq!(Foo::A.into_bar(), Bar::X);
+        assert_eq!(Foo::B(2).into_bar(), Bar::Y(2));
+        assert_eq!(Foo::from_bar(Bar::Y(3)), Foo::B(3));
 
-    use super::*;
+        assert_eq!(Bar::X.into_foo(), Foo::A);
+        assert_eq!(Bar::from_foo(Foo::B(4)), Bar::Y(4));
+    }
 
-    mod context_usage_tests {
-        mod context_mod {
-            enum Foo {
+    #[test]
+    fn methods_shared_visibility() {
+        mod restricted {
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Foo {
                 A,
                 B,
             }
-            enum Bar {
+
+            #[derive(Debug, PartialEq, Clone)]
+            pub enum Bar {
                 X,
                 Y,
             }
 
-            // Can be used within modules
-            bijection!(Foo, Bar, {
+            bijection_methods!(pub(crate) Foo { into_bar, from_bar }, Bar { into_foo, from_foo }, {
                 Foo::A => Bar::X,
                 Foo::B => Bar::Y,
             });
         }
 
-        mod context_fn {
-            #[allow(dead_code)]
-            fn foo() {
-                enum Foo {
-                    A,
-                    B,
-                }
-                enum Bar {
-                    X,
-                    Y,
-                }
+        mod public {
+            #[derive(Debug, PartialEq, Clone)]
+            pub struct Meters(pub u32);
 
-                // Can be used within functions
-                bijection!(Foo, Bar, {
-                    Foo::A => Bar::X,
-                    Foo::B => Bar::Y,
-                });
-            }
+            bijection_methods!(pub Meters { into_millis, from_millis }, u32, {
+                Meters(m) => m,
+            });
         }
-    }
 
-    /// Asserts equality in both directions,
-    /// and that `T` and `U` both implement `From`/`Into` for one another.
-    fn test_bijection_eq<T, U>(t: T, u: U)
-    where
-        T: From<U> + PartialEq + Debug + Clone,
-        U: From<T> + PartialEq + Debug + Clone,
-    {
-        assert_eq!(U::from(t.clone()), u.clone());
-        assert_eq!(T::from(u), t);
+        use public::Meters;
+        use restricted::{Bar, Foo};
+
+        // Only callable from outside of the modules with the given visibility
+        assert_eq!(Foo::A.into_bar(), Bar::X);
+        assert_eq!(Foo::from_bar(Bar::Y), Foo::B);
+        assert_eq!(Bar::Y.into_foo(), Foo::B);
+        assert_eq!(Bar::from_foo(Foo::A), Bar::X);
+
+        assert_eq!(Meters(3).into_millis(), 3);
+        assert_eq!(Meters::from_millis(4), Meters(4));
     }
 
     #[test]
-    fn empty_enum() {
+    fn methods_foreign_type() {
         #[derive(Debug, PartialEq, Clone)]
-        enum Foo {}
+        enum Tristate {
+            Neutral,
+            Positive,
+            Negative,
+        }
 
-        #[derive(Debug, PartialEq, Clone)]
-        enum Bar {}
+        // No methods can be added to Option<bool>
+        bijection_methods!(Option<bool>, Tristate { into_option, from_option }, {
+            None => Tristate::Neutral,
+            Some(true) => Tristate::Positive,
+            Some(false) => Tristate::Negative,
+        });
 
-        bijection!(Foo, Bar, {});
+        assert_eq!(Tristate::Positive.into_option(), Some(true));
+        assert_eq!(Tristate::from_option(None), Tristate::Neutral);
+        assert_eq!(Tristate::from_option(Some(false)), Tristate::Negative);
     }
 
     #[test]
-    fn one_variant() {
+    fn cfg_branches() {
+        // `cfg(any())` is always disabled, like a feature that is turned off
         #[derive(Debug, PartialEq, Clone)]
         enum Foo {
             A,
+            B(u8),
+            #[cfg(any())]
+            Extra,
         }
 
         #[derive(Debug, PartialEq, Clone)]
         enum Bar {
             X,
+            Y(u8),
+            #[cfg(any())]
+            Extra,
         }
 
         bijection!(Foo, Bar, {
             Foo::A => Bar::X,
+            #[cfg(all())]
+            Foo::B(b) => Bar::Y(b),
+            #[cfg(any())]
+            Foo::Extra => Bar::Extra,
         });
 
         test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
     }
 
     #[test]
-    fn two_variants() {
+    fn cfg_branches_alternatives() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        // Only one of the alternatives is compiled, so they may share a right-hand side
+        bijection!(Op, u8, {
+            #[cfg(all())]
+            Op::Add => 1,
+            #[cfg(any())]
+            Op::Add => 2,
+            #[cfg(any())]
+            Op::Sub => 1,
+            #[cfg(all())]
+            Op::Sub => 2; reverse 2 => Op::Sub,
+        });
+
+        assert_eq!(u8::from(Op::Add), 1);
+        assert_eq!(u8::from(Op::Sub), 2);
+        assert_eq!(Op::try_from(1), Ok(Op::Add));
+        assert_eq!(Op::try_from(2), Ok(Op::Sub));
+    }
+
+    #[test]
+    fn binding_ranges() {
         #[derive(Debug, PartialEq, Clone)]
         enum Foo {
-            A,
-            B,
+            Num(u8),
         }
 
         #[derive(Debug, PartialEq, Clone)]
         enum Bar {
-            X,
-            Y,
+            Digit(u8),
+            Number(u8),
         }
 
         bijection!(Foo, Bar, {
-            Foo::A => Bar::X,
-            Foo::B => Bar::Y,
+            Foo::Num(n @ 0..=9) => Bar::Digit(n),
+            Foo::Num(n) => Bar::Number(n),
         });
 
-        test_bijection_eq(Foo::A, Bar::X);
-        test_bijection_eq(Foo::B, Bar::Y);
+        test_bijection_eq(Foo::Num(0), Bar::Digit(0));
+        test_bijection_eq(Foo::Num(9), Bar::Digit(9));
+        test_bijection_eq(Foo::Num(10), Bar::Number(10));
+
+        // The reverse arms don't check the range
+        assert_eq!(Foo::from(Bar::Digit(42)), Foo::Num(42));
     }
 
     #[test]
-    fn struct_point() {
+    fn binding_ranges_nested() {
         #[derive(Debug, PartialEq, Clone)]
-        struct Point {
-            x: i32,
-            y: i32,
+        enum Foo {
+            Pair { low: u8, high: u8 },
+            Wrapped(Option<(i8, i8)>),
         }
 
         #[derive(Debug, PartialEq, Clone)]
-        struct PointFlipped {
-            y: i32,
-            x: i32,
+        enum Bar {
+            Small(u8, u8),
+            Large(u8, u8),
+            Signed(i8, i8),
+            Other(Option<(i8, i8)>),
         }
 
-        bijection!(Point, PointFlipped, {
-            Point { x, y } => PointFlipped { y: x, x: y }
+        bijection!(Foo, Bar, {
+            Foo::Pair { low: l @ 0..=9, high } => Bar::Small(l, high),
+            Foo::Pair { low, high } => Bar::Large(low, high),
+            Foo::Wrapped(Some((x @ -9..=9, y))) => Bar::Signed(x, y),
+            Foo::Wrapped(other) => Bar::Other(other),
         });
 
-        test_bijection_eq(Point { x: 5, y: 10 }, PointFlipped { x: 10, y: 5 });
-        test_bijection_eq(Point { x: 20, y: -20 }, PointFlipped { x: -20, y: 20 });
+        test_bijection_eq(Foo::Pair { low: 3, high: 200 }, Bar::Small(3, 200));
+        test_bijection_eq(Foo::Pair { low: 30, high: 200 }, Bar::Large(30, 200));
+        test_bijection_eq(Foo::Wrapped(Some((-3, 4))), Bar::Signed(-3, 4));
+        test_bijection_eq(Foo::Wrapped(None), Bar::Other(None));
     }
 
     #[test]
-    fn struct_enum() {
+    fn trait_impls() {
+        trait Translate<T> {
+            fn convert(self) -> T;
+        }
+
         #[derive(Debug, PartialEq, Clone)]
-        struct Point {
-            x: i32,
-            y: i32,
+        enum Foo {
+            A,
+            B(u8),
+            C { x: i32 },
         }
 
         #[derive(Debug, PartialEq, Clone)]
-        enum PointEnum {
-            Zero,
-            OneOne,
-            Other { x: i32, y: i32 },
+        enum Bar {
+            X,
+            Y(u8),
+            Z(i32),
         }
 
-        bijection!(Point, PointEnum, {
-            Point { x: 0, y: 0 } => PointEnum::Zero,
-            Point { x: 1, y: 1 } => PointEnum::OneOne,
-            Point { x, y } => PointEnum::Other { x, y },
+        bijection_trait!(Translate, Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(n) => Bar::Y(n),
+            Foo::C { x } => Bar::Z(x),
         });
 
-        test_bijection_eq(Point { x: 0, y: 0 }, PointEnum::Zero);
-        test_bijection_eq(Point { x: 1, y: 1 }, PointEnum::OneOne);
-        test_bijection_eq(Point { x: 5, y: 10 }, PointEnum::Other { x: 5, y: 10 });
-        test_bijection_eq(Point { x: 20, y: -20 }, PointEnum::Other { x: 20, y: -20 });
+        assert_eq!(Translate::<Bar>::convert(Foo::A), Bar::X);
+        assert_eq!(Translate::<Bar>::convert(Foo::B(2)), Bar::Y(2));
+        assert_eq!(Translate::<Foo>::convert(Bar::Z(-1)), Foo::C { x: -1 });
+        let foo: Foo = Bar::Y(5).convert();
+        assert_eq!(foo, Foo::B(5));
     }
 
     #[test]
-    fn unreachable_patterns() {
+    fn same_names() {
         #[derive(Debug, PartialEq, Clone)]
-        struct Foo(i32);
+        enum WireColor {
+            Red,
+            Green,
+            Blue,
+        }
 
         #[derive(Debug, PartialEq, Clone)]
-        struct Bar(i32);
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
 
-        // This should complain with a warning! (hence the deny attribute)
-        #[deny(unfulfilled_lint_expectations)]
-        #[expect(unreachable_patterns)]
-        {
-            bijection!(Foo, Bar, {
-                Foo(0) => Bar(0),
-                Foo(1) => Bar(0),
-                Foo(1) => Bar(1),
-                Foo(x) => Bar(x),
-            });
+        bijection_same_names!(WireColor, Color, [Red, Green, Blue]);
+
+        test_bijection_eq(WireColor::Red, Color::Red);
+        test_bijection_eq(WireColor::Green, Color::Green);
+        test_bijection_eq(WireColor::Blue, Color::Blue);
+    }
+
+    #[test]
+    fn enum_index() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Op {
+            Add,
+            Sub,
+            Mul,
+            Div,
         }
 
-        assert_eq!(Bar::from(Foo(0)), Bar(0));
-        assert_eq!(Foo::from(Bar(0)), Foo(0));
+        bijection_enum_index!(
+            #[by_ref]
+            Op,
+            u32,
+            [Add, Sub, Mul, Div]
+        );
 
-        assert_eq!(Bar::from(Foo(1)), Bar(0));
-        assert_ne!(Foo::from(Bar(1)), Foo(0));
-        assert_eq!(Foo::from(Bar(1)), Foo(1));
+        assert_eq!(u32::from(Op::Mul), 2);
+        assert_eq!(Op::try_from(2), Ok(Op::Mul));
+        assert_eq!(u32::from(&Op::Add), 0);
+        assert_eq!(Op::try_from(4), Err(4));
+        for op in [Op::Add, Op::Sub, Op::Mul, Op::Div] {
+            assert_eq!(u32::from(op), op as u32);
+            assert_eq!(Op::try_from(op as u32), Ok(op));
+        }
+    }
 
-        assert_eq!(Bar::from(Foo(3)), Bar(3));
-        assert_eq!(Foo::from(Bar(3)), Foo(3));
+    #[test]
+    fn enum_index_long() {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Letter {
+            A,
+            B,
+            C,
+            D,
+            E,
+            F,
+            G,
+            H,
+            I,
+            J,
+            K,
+            L,
+            M,
+            N,
+            O,
+            P,
+            Q,
+            R,
+            S,
+            T,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct NotALetter(u8);
+
+        impl From<u8> for NotALetter {
+            fn from(value: u8) -> Self {
+                NotALetter(value)
+            }
+        }
+
+        bijection_enum_index!(
+            Letter,
+            u8,
+            error = NotALetter,
+            [A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T,]
+        );
+
+        assert_eq!(u8::from(Letter::A), 0);
+        assert_eq!(u8::from(Letter::I), 8);
+        assert_eq!(u8::from(Letter::T), 19);
+        assert_eq!(Letter::try_from(16), Ok(Letter::Q));
+        assert_eq!(Letter::try_from(20), Err(NotALetter(20)));
     }
 
     #[test]
-    fn external_type() {
+    fn str_display_and_parse() {
+        extern crate alloc;
+        use alloc::format;
+        use alloc::string::ToString;
+
         #[derive(Debug, PartialEq, Clone)]
-        enum Tristate {
-            Neutral,
-            Positive,
-            Negative,
+        enum Mode {
+            Fast,
+            Slow,
         }
 
-        bijection!(Option<bool>, Tristate, {
-            None => Tristate::Neutral,
-            Some(true) => Tristate::Positive,
-            Some(false) => Tristate::Negative,
+        str_bijection!(Mode, {
+            Mode::Fast => "fast",
+            Mode::Slow => "slow",
         });
 
-        test_bijection_eq(Tristate::Neutral, None::<bool>);
-        test_bijection_eq(Tristate::Positive, Some(true));
-        test_bijection_eq(Tristate::Negative, Some(false));
+        assert_eq!(Mode::Fast.to_string(), "fast");
+        assert_eq!(format!("[{:>6}]", Mode::Slow), "[  slow]");
+
+        assert_eq!("fast".parse::<Mode>(), Ok(Mode::Fast));
+        assert_eq!("slow".parse::<Mode>(), Ok(Mode::Slow));
+        assert_eq!("Fast".parse::<Mode>(), Err(crate::ParseError));
+        assert_eq!("".parse::<Mode>(), Err(crate::ParseError));
     }
 
-    // TODO: Make it fail on inner or-patterns (which makes it behave like a bitwise or!!!)
-    // Example Foo(2 | 3) => Bar(1),
+    #[test]
+    fn str_custom_error() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Mode {
+            Fast,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct UnknownMode(usize);
+
+        impl From<&str> for UnknownMode {
+            fn from(value: &str) -> Self {
+                UnknownMode(value.len())
+            }
+        }
+
+        str_bijection!(Mode, error = UnknownMode, {
+            Mode::Fast => "fast",
+        });
+
+        assert_eq!("fast".parse::<Mode>(), Ok(Mode::Fast));
+        assert_eq!("slow!".parse::<Mode>(), Err(UnknownMode(5)));
+    }
+
+    #[test]
+    fn chain_three_enums() {
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Baz {
+            First,
+            Second { value: u8 },
+        }
+
+        bijection_chain!(Foo, Bar, Baz, {
+            a_b: {
+                Foo::A => Bar::X,
+                Foo::B(b) => Bar::Y(b),
+            },
+            b_c: {
+                Bar::X => Baz::First,
+                Bar::Y(value) => Baz::Second { value },
+            },
+        });
+
+        test_bijection_eq(Foo::A, Bar::X);
+        test_bijection_eq(Foo::B(1), Bar::Y(1));
+        test_bijection_eq(Bar::X, Baz::First);
+        test_bijection_eq(Bar::Y(2), Baz::Second { value: 2 });
+
+        // Composed through Bar
+        test_bijection_eq(Foo::A, Baz::First);
+        test_bijection_eq(Foo::B(3), Baz::Second { value: 3 });
+    }
+
+    #[test]
+    fn container_elements() {
+        extern crate alloc;
+        use alloc::collections::VecDeque;
+        use alloc::vec::Vec;
+
+        trait Convert<T> {
+            fn convert(self) -> T;
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Foo {
+            A,
+            B(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        enum Bar {
+            X,
+            Y(u8),
+        }
+
+        #[derive(Debug, PartialEq, Clone)]
+        struct Stack<T>(Vec<T>);
+
+        impl<T> IntoIterator for Stack<T> {
+            type Item = T;
+            type IntoIter = alloc::vec::IntoIter<T>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        impl<T> FromIterator<T> for Stack<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                Stack(iter.into_iter().collect())
+            }
+        }
+
+        bijection!(Foo, Bar, {
+            Foo::A => Bar::X,
+            Foo::B(n) => Bar::Y(n),
+        });
+
+        bijection_container!(Stack<Foo>, Stack<Bar>);
+        bijection_container!(Convert, VecDeque<Foo>, VecDeque<Bar>);
+
+        test_bijection_eq(
+            Stack(alloc::vec![Foo::A, Foo::B(1)]),
+            Stack(alloc::vec![Bar::X, Bar::Y(1)]),
+        );
+        test_bijection_eq(Stack(Vec::<Foo>::new()), Stack(Vec::<Bar>::new()));
+
+        let foos = VecDeque::from([Foo::B(2), Foo::A, Foo::B(3)]);
+        let bars: VecDeque<Bar> = foos.clone().convert();
+        assert_eq!(bars, [Bar::Y(2), Bar::X, Bar::Y(3)]);
+        assert_eq!(Convert::<VecDeque<Foo>>::convert(bars), foos);
+    }
 
-    // TODO: Compiler error tests
+    // Compiler errors are tested in `tests/ui` (see `tests/compile_fail.rs`)
 
     // // Used for testing compiler errors etc.
     // #[test]
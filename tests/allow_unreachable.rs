@@ -0,0 +1,23 @@
+//! `#[allow_unreachable]` in another crate, where the lint is denied
+//! (see `tests/ui/unreachable_without_allow.rs` for the same bijection without the flag).
+
+#![deny(unreachable_patterns)]
+
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Foo(u8);
+
+#[derive(Debug, PartialEq, Clone)]
+struct Bar(u8);
+
+bijection!(#[allow_unreachable] Foo, Bar, {
+    Foo(x) => Bar(x),
+    Foo(0) => Bar(0); reverse Bar(0) => Foo(0),
+});
+
+#[test]
+fn allow_unreachable() {
+    assert_eq!(Bar::from(Foo(0)), Bar(0));
+    assert_eq!(Foo::from(Bar(7)), Foo(7));
+}
@@ -0,0 +1,22 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+// The same mistakes as in `ui`, with the errors pointing at the offending tokens instead of the whole invocation
+#[cfg(feature = "proc")]
+#[test]
+fn compile_fail_proc() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/proc/*.rs");
+    t.pass("tests/ui/proc/pass/*.rs");
+}
+
+// Flags which need `alloc`, built with `default-features = false`
+#[cfg(not(feature = "alloc"))]
+#[test]
+fn compile_fail_no_alloc() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/no_alloc/*.rs");
+}
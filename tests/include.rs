@@ -0,0 +1,30 @@
+//! Branches kept in a separate file, by including the whole invocation.
+
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Foo {
+    A,
+    B(u8),
+    C { value: i32 },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Bar {
+    X,
+    Y(u8),
+    Z(i32),
+}
+
+include!("data/foo_bar.in");
+
+#[test]
+fn included_invocation() {
+    assert_eq!(Bar::from(Foo::A), Bar::X);
+    assert_eq!(Bar::from(Foo::B(3)), Bar::Y(3));
+    assert_eq!(Bar::from(Foo::C { value: -5 }), Bar::Z(-5));
+
+    assert_eq!(Foo::from(Bar::X), Foo::A);
+    assert_eq!(Foo::from(Bar::Y(3)), Foo::B(3));
+    assert_eq!(Foo::from(Bar::Z(-5)), Foo::C { value: -5 });
+}
@@ -0,0 +1,1185 @@
+//! Expansion of large bijections, which should not need a raised `recursion_limit`.
+
+use biject_into::{bijection, bijection_enum_index};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Foo {
+    A0,
+    A1(u8),
+    A2,
+    A3(u8),
+    A4,
+    A5(u8),
+    A6,
+    A7(u8),
+    A8,
+    A9(u8),
+    A10,
+    A11(u8),
+    A12,
+    A13(u8),
+    A14,
+    A15(u8),
+    A16,
+    A17(u8),
+    A18,
+    A19(u8),
+    A20,
+    A21(u8),
+    A22,
+    A23(u8),
+    A24,
+    A25(u8),
+    A26,
+    A27(u8),
+    A28,
+    A29(u8),
+    A30,
+    A31(u8),
+    A32,
+    A33(u8),
+    A34,
+    A35(u8),
+    A36,
+    A37(u8),
+    A38,
+    A39(u8),
+    A40,
+    A41(u8),
+    A42,
+    A43(u8),
+    A44,
+    A45(u8),
+    A46,
+    A47(u8),
+    A48,
+    A49(u8),
+    A50,
+    A51(u8),
+    A52,
+    A53(u8),
+    A54,
+    A55(u8),
+    A56,
+    A57(u8),
+    A58,
+    A59(u8),
+    A60,
+    A61(u8),
+    A62,
+    A63(u8),
+    A64,
+    A65(u8),
+    A66,
+    A67(u8),
+    A68,
+    A69(u8),
+    A70,
+    A71(u8),
+    A72,
+    A73(u8),
+    A74,
+    A75(u8),
+    A76,
+    A77(u8),
+    A78,
+    A79(u8),
+    A80,
+    A81(u8),
+    A82,
+    A83(u8),
+    A84,
+    A85(u8),
+    A86,
+    A87(u8),
+    A88,
+    A89(u8),
+    A90,
+    A91(u8),
+    A92,
+    A93(u8),
+    A94,
+    A95(u8),
+    A96,
+    A97(u8),
+    A98,
+    A99(u8),
+    A100,
+    A101(u8),
+    A102,
+    A103(u8),
+    A104,
+    A105(u8),
+    A106,
+    A107(u8),
+    A108,
+    A109(u8),
+    A110,
+    A111(u8),
+    A112,
+    A113(u8),
+    A114,
+    A115(u8),
+    A116,
+    A117(u8),
+    A118,
+    A119(u8),
+    A120,
+    A121(u8),
+    A122,
+    A123(u8),
+    A124,
+    A125(u8),
+    A126,
+    A127(u8),
+    A128,
+    A129(u8),
+    A130,
+    A131(u8),
+    A132,
+    A133(u8),
+    A134,
+    A135(u8),
+    A136,
+    A137(u8),
+    A138,
+    A139(u8),
+    A140,
+    A141(u8),
+    A142,
+    A143(u8),
+    A144,
+    A145(u8),
+    A146,
+    A147(u8),
+    A148,
+    A149(u8),
+    A150,
+    A151(u8),
+    A152,
+    A153(u8),
+    A154,
+    A155(u8),
+    A156,
+    A157(u8),
+    A158,
+    A159(u8),
+    A160,
+    A161(u8),
+    A162,
+    A163(u8),
+    A164,
+    A165(u8),
+    A166,
+    A167(u8),
+    A168,
+    A169(u8),
+    A170,
+    A171(u8),
+    A172,
+    A173(u8),
+    A174,
+    A175(u8),
+    A176,
+    A177(u8),
+    A178,
+    A179(u8),
+    A180,
+    A181(u8),
+    A182,
+    A183(u8),
+    A184,
+    A185(u8),
+    A186,
+    A187(u8),
+    A188,
+    A189(u8),
+    A190,
+    A191(u8),
+    A192,
+    A193(u8),
+    A194,
+    A195(u8),
+    A196,
+    A197(u8),
+    A198,
+    A199(u8),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Bar {
+    X0,
+    X1(u8),
+    X2,
+    X3(u8),
+    X4,
+    X5(u8),
+    X6,
+    X7(u8),
+    X8,
+    X9(u8),
+    X10,
+    X11(u8),
+    X12,
+    X13(u8),
+    X14,
+    X15(u8),
+    X16,
+    X17(u8),
+    X18,
+    X19(u8),
+    X20,
+    X21(u8),
+    X22,
+    X23(u8),
+    X24,
+    X25(u8),
+    X26,
+    X27(u8),
+    X28,
+    X29(u8),
+    X30,
+    X31(u8),
+    X32,
+    X33(u8),
+    X34,
+    X35(u8),
+    X36,
+    X37(u8),
+    X38,
+    X39(u8),
+    X40,
+    X41(u8),
+    X42,
+    X43(u8),
+    X44,
+    X45(u8),
+    X46,
+    X47(u8),
+    X48,
+    X49(u8),
+    X50,
+    X51(u8),
+    X52,
+    X53(u8),
+    X54,
+    X55(u8),
+    X56,
+    X57(u8),
+    X58,
+    X59(u8),
+    X60,
+    X61(u8),
+    X62,
+    X63(u8),
+    X64,
+    X65(u8),
+    X66,
+    X67(u8),
+    X68,
+    X69(u8),
+    X70,
+    X71(u8),
+    X72,
+    X73(u8),
+    X74,
+    X75(u8),
+    X76,
+    X77(u8),
+    X78,
+    X79(u8),
+    X80,
+    X81(u8),
+    X82,
+    X83(u8),
+    X84,
+    X85(u8),
+    X86,
+    X87(u8),
+    X88,
+    X89(u8),
+    X90,
+    X91(u8),
+    X92,
+    X93(u8),
+    X94,
+    X95(u8),
+    X96,
+    X97(u8),
+    X98,
+    X99(u8),
+    X100,
+    X101(u8),
+    X102,
+    X103(u8),
+    X104,
+    X105(u8),
+    X106,
+    X107(u8),
+    X108,
+    X109(u8),
+    X110,
+    X111(u8),
+    X112,
+    X113(u8),
+    X114,
+    X115(u8),
+    X116,
+    X117(u8),
+    X118,
+    X119(u8),
+    X120,
+    X121(u8),
+    X122,
+    X123(u8),
+    X124,
+    X125(u8),
+    X126,
+    X127(u8),
+    X128,
+    X129(u8),
+    X130,
+    X131(u8),
+    X132,
+    X133(u8),
+    X134,
+    X135(u8),
+    X136,
+    X137(u8),
+    X138,
+    X139(u8),
+    X140,
+    X141(u8),
+    X142,
+    X143(u8),
+    X144,
+    X145(u8),
+    X146,
+    X147(u8),
+    X148,
+    X149(u8),
+    X150,
+    X151(u8),
+    X152,
+    X153(u8),
+    X154,
+    X155(u8),
+    X156,
+    X157(u8),
+    X158,
+    X159(u8),
+    X160,
+    X161(u8),
+    X162,
+    X163(u8),
+    X164,
+    X165(u8),
+    X166,
+    X167(u8),
+    X168,
+    X169(u8),
+    X170,
+    X171(u8),
+    X172,
+    X173(u8),
+    X174,
+    X175(u8),
+    X176,
+    X177(u8),
+    X178,
+    X179(u8),
+    X180,
+    X181(u8),
+    X182,
+    X183(u8),
+    X184,
+    X185(u8),
+    X186,
+    X187(u8),
+    X188,
+    X189(u8),
+    X190,
+    X191(u8),
+    X192,
+    X193(u8),
+    X194,
+    X195(u8),
+    X196,
+    X197(u8),
+    X198,
+    X199(u8),
+}
+
+bijection!(Foo, Bar, {
+    Foo::A0 => Bar::X0,
+    Foo::A1(x) => Bar::X1(x),
+    Foo::A2 => Bar::X2,
+    Foo::A3(x) => Bar::X3(x),
+    Foo::A4 => Bar::X4,
+    Foo::A5(x) => Bar::X5(x),
+    Foo::A6 => Bar::X6,
+    Foo::A7(x) => Bar::X7(x),
+    Foo::A8 => Bar::X8,
+    Foo::A9(x) => Bar::X9(x),
+    Foo::A10 => Bar::X10,
+    Foo::A11(x) => Bar::X11(x),
+    Foo::A12 => Bar::X12,
+    Foo::A13(x) => Bar::X13(x),
+    Foo::A14 => Bar::X14,
+    Foo::A15(x) => Bar::X15(x),
+    Foo::A16 => Bar::X16,
+    Foo::A17(x) => Bar::X17(x),
+    Foo::A18 => Bar::X18,
+    Foo::A19(x) => Bar::X19(x),
+    Foo::A20 => Bar::X20,
+    Foo::A21(x) => Bar::X21(x),
+    Foo::A22 => Bar::X22,
+    Foo::A23(x) => Bar::X23(x),
+    Foo::A24 => Bar::X24,
+    Foo::A25(x) => Bar::X25(x),
+    Foo::A26 => Bar::X26,
+    Foo::A27(x) => Bar::X27(x),
+    Foo::A28 => Bar::X28,
+    Foo::A29(x) => Bar::X29(x),
+    Foo::A30 => Bar::X30,
+    Foo::A31(x) => Bar::X31(x),
+    Foo::A32 => Bar::X32,
+    Foo::A33(x) => Bar::X33(x),
+    Foo::A34 => Bar::X34,
+    Foo::A35(x) => Bar::X35(x),
+    Foo::A36 => Bar::X36,
+    Foo::A37(x) => Bar::X37(x),
+    Foo::A38 => Bar::X38,
+    Foo::A39(x) => Bar::X39(x),
+    Foo::A40 => Bar::X40,
+    Foo::A41(x) => Bar::X41(x),
+    Foo::A42 => Bar::X42,
+    Foo::A43(x) => Bar::X43(x),
+    Foo::A44 => Bar::X44,
+    Foo::A45(x) => Bar::X45(x),
+    Foo::A46 => Bar::X46,
+    Foo::A47(x) => Bar::X47(x),
+    Foo::A48 => Bar::X48,
+    Foo::A49(x) => Bar::X49(x),
+    Foo::A50 => Bar::X50,
+    Foo::A51(x) => Bar::X51(x),
+    Foo::A52 => Bar::X52,
+    Foo::A53(x) => Bar::X53(x),
+    Foo::A54 => Bar::X54,
+    Foo::A55(x) => Bar::X55(x),
+    Foo::A56 => Bar::X56,
+    Foo::A57(x) => Bar::X57(x),
+    Foo::A58 => Bar::X58,
+    Foo::A59(x) => Bar::X59(x),
+    Foo::A60 => Bar::X60,
+    Foo::A61(x) => Bar::X61(x),
+    Foo::A62 => Bar::X62,
+    Foo::A63(x) => Bar::X63(x),
+    Foo::A64 => Bar::X64,
+    Foo::A65(x) => Bar::X65(x),
+    Foo::A66 => Bar::X66,
+    Foo::A67(x) => Bar::X67(x),
+    Foo::A68 => Bar::X68,
+    Foo::A69(x) => Bar::X69(x),
+    Foo::A70 => Bar::X70,
+    Foo::A71(x) => Bar::X71(x),
+    Foo::A72 => Bar::X72,
+    Foo::A73(x) => Bar::X73(x),
+    Foo::A74 => Bar::X74,
+    Foo::A75(x) => Bar::X75(x),
+    Foo::A76 => Bar::X76,
+    Foo::A77(x) => Bar::X77(x),
+    Foo::A78 => Bar::X78,
+    Foo::A79(x) => Bar::X79(x),
+    Foo::A80 => Bar::X80,
+    Foo::A81(x) => Bar::X81(x),
+    Foo::A82 => Bar::X82,
+    Foo::A83(x) => Bar::X83(x),
+    Foo::A84 => Bar::X84,
+    Foo::A85(x) => Bar::X85(x),
+    Foo::A86 => Bar::X86,
+    Foo::A87(x) => Bar::X87(x),
+    Foo::A88 => Bar::X88,
+    Foo::A89(x) => Bar::X89(x),
+    Foo::A90 => Bar::X90,
+    Foo::A91(x) => Bar::X91(x),
+    Foo::A92 => Bar::X92,
+    Foo::A93(x) => Bar::X93(x),
+    Foo::A94 => Bar::X94,
+    Foo::A95(x) => Bar::X95(x),
+    Foo::A96 => Bar::X96,
+    Foo::A97(x) => Bar::X97(x),
+    Foo::A98 => Bar::X98,
+    Foo::A99(x) => Bar::X99(x),
+    Foo::A100 => Bar::X100,
+    Foo::A101(x) => Bar::X101(x),
+    Foo::A102 => Bar::X102,
+    Foo::A103(x) => Bar::X103(x),
+    Foo::A104 => Bar::X104,
+    Foo::A105(x) => Bar::X105(x),
+    Foo::A106 => Bar::X106,
+    Foo::A107(x) => Bar::X107(x),
+    Foo::A108 => Bar::X108,
+    Foo::A109(x) => Bar::X109(x),
+    Foo::A110 => Bar::X110,
+    Foo::A111(x) => Bar::X111(x),
+    Foo::A112 => Bar::X112,
+    Foo::A113(x) => Bar::X113(x),
+    Foo::A114 => Bar::X114,
+    Foo::A115(x) => Bar::X115(x),
+    Foo::A116 => Bar::X116,
+    Foo::A117(x) => Bar::X117(x),
+    Foo::A118 => Bar::X118,
+    Foo::A119(x) => Bar::X119(x),
+    Foo::A120 => Bar::X120,
+    Foo::A121(x) => Bar::X121(x),
+    Foo::A122 => Bar::X122,
+    Foo::A123(x) => Bar::X123(x),
+    Foo::A124 => Bar::X124,
+    Foo::A125(x) => Bar::X125(x),
+    Foo::A126 => Bar::X126,
+    Foo::A127(x) => Bar::X127(x),
+    Foo::A128 => Bar::X128,
+    Foo::A129(x) => Bar::X129(x),
+    Foo::A130 => Bar::X130,
+    Foo::A131(x) => Bar::X131(x),
+    Foo::A132 => Bar::X132,
+    Foo::A133(x) => Bar::X133(x),
+    Foo::A134 => Bar::X134,
+    Foo::A135(x) => Bar::X135(x),
+    Foo::A136 => Bar::X136,
+    Foo::A137(x) => Bar::X137(x),
+    Foo::A138 => Bar::X138,
+    Foo::A139(x) => Bar::X139(x),
+    Foo::A140 => Bar::X140,
+    Foo::A141(x) => Bar::X141(x),
+    Foo::A142 => Bar::X142,
+    Foo::A143(x) => Bar::X143(x),
+    Foo::A144 => Bar::X144,
+    Foo::A145(x) => Bar::X145(x),
+    Foo::A146 => Bar::X146,
+    Foo::A147(x) => Bar::X147(x),
+    Foo::A148 => Bar::X148,
+    Foo::A149(x) => Bar::X149(x),
+    Foo::A150 => Bar::X150,
+    Foo::A151(x) => Bar::X151(x),
+    Foo::A152 => Bar::X152,
+    Foo::A153(x) => Bar::X153(x),
+    Foo::A154 => Bar::X154,
+    Foo::A155(x) => Bar::X155(x),
+    Foo::A156 => Bar::X156,
+    Foo::A157(x) => Bar::X157(x),
+    Foo::A158 => Bar::X158,
+    Foo::A159(x) => Bar::X159(x),
+    Foo::A160 => Bar::X160,
+    Foo::A161(x) => Bar::X161(x),
+    Foo::A162 => Bar::X162,
+    Foo::A163(x) => Bar::X163(x),
+    Foo::A164 => Bar::X164,
+    Foo::A165(x) => Bar::X165(x),
+    Foo::A166 => Bar::X166,
+    Foo::A167(x) => Bar::X167(x),
+    Foo::A168 => Bar::X168,
+    Foo::A169(x) => Bar::X169(x),
+    Foo::A170 => Bar::X170,
+    Foo::A171(x) => Bar::X171(x),
+    Foo::A172 => Bar::X172,
+    Foo::A173(x) => Bar::X173(x),
+    Foo::A174 => Bar::X174,
+    Foo::A175(x) => Bar::X175(x),
+    Foo::A176 => Bar::X176,
+    Foo::A177(x) => Bar::X177(x),
+    Foo::A178 => Bar::X178,
+    Foo::A179(x) => Bar::X179(x),
+    Foo::A180 => Bar::X180,
+    Foo::A181(x) => Bar::X181(x),
+    Foo::A182 => Bar::X182,
+    Foo::A183(x) => Bar::X183(x),
+    Foo::A184 => Bar::X184,
+    Foo::A185(x) => Bar::X185(x),
+    Foo::A186 => Bar::X186,
+    Foo::A187(x) => Bar::X187(x),
+    Foo::A188 => Bar::X188,
+    Foo::A189(x) => Bar::X189(x),
+    Foo::A190 => Bar::X190,
+    Foo::A191(x) => Bar::X191(x),
+    Foo::A192 => Bar::X192,
+    Foo::A193(x) => Bar::X193(x),
+    Foo::A194 => Bar::X194,
+    Foo::A195(x) => Bar::X195(x),
+    Foo::A196 => Bar::X196,
+    Foo::A197(x) => Bar::X197(x),
+    Foo::A198 => Bar::X198,
+    // After a long run of plain branches
+    Foo::A199(x) => Bar::X199(x); reverse Bar::X199(x) => Foo::A199(x),
+});
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Byte {
+    B0,
+    B1,
+    B2,
+    B3,
+    B4,
+    B5,
+    B6,
+    B7,
+    B8,
+    B9,
+    B10,
+    B11,
+    B12,
+    B13,
+    B14,
+    B15,
+    B16,
+    B17,
+    B18,
+    B19,
+    B20,
+    B21,
+    B22,
+    B23,
+    B24,
+    B25,
+    B26,
+    B27,
+    B28,
+    B29,
+    B30,
+    B31,
+    B32,
+    B33,
+    B34,
+    B35,
+    B36,
+    B37,
+    B38,
+    B39,
+    B40,
+    B41,
+    B42,
+    B43,
+    B44,
+    B45,
+    B46,
+    B47,
+    B48,
+    B49,
+    B50,
+    B51,
+    B52,
+    B53,
+    B54,
+    B55,
+    B56,
+    B57,
+    B58,
+    B59,
+    B60,
+    B61,
+    B62,
+    B63,
+    B64,
+    B65,
+    B66,
+    B67,
+    B68,
+    B69,
+    B70,
+    B71,
+    B72,
+    B73,
+    B74,
+    B75,
+    B76,
+    B77,
+    B78,
+    B79,
+    B80,
+    B81,
+    B82,
+    B83,
+    B84,
+    B85,
+    B86,
+    B87,
+    B88,
+    B89,
+    B90,
+    B91,
+    B92,
+    B93,
+    B94,
+    B95,
+    B96,
+    B97,
+    B98,
+    B99,
+    B100,
+    B101,
+    B102,
+    B103,
+    B104,
+    B105,
+    B106,
+    B107,
+    B108,
+    B109,
+    B110,
+    B111,
+    B112,
+    B113,
+    B114,
+    B115,
+    B116,
+    B117,
+    B118,
+    B119,
+    B120,
+    B121,
+    B122,
+    B123,
+    B124,
+    B125,
+    B126,
+    B127,
+    B128,
+    B129,
+    B130,
+    B131,
+    B132,
+    B133,
+    B134,
+    B135,
+    B136,
+    B137,
+    B138,
+    B139,
+    B140,
+    B141,
+    B142,
+    B143,
+    B144,
+    B145,
+    B146,
+    B147,
+    B148,
+    B149,
+    B150,
+    B151,
+    B152,
+    B153,
+    B154,
+    B155,
+    B156,
+    B157,
+    B158,
+    B159,
+    B160,
+    B161,
+    B162,
+    B163,
+    B164,
+    B165,
+    B166,
+    B167,
+    B168,
+    B169,
+    B170,
+    B171,
+    B172,
+    B173,
+    B174,
+    B175,
+    B176,
+    B177,
+    B178,
+    B179,
+    B180,
+    B181,
+    B182,
+    B183,
+    B184,
+    B185,
+    B186,
+    B187,
+    B188,
+    B189,
+    B190,
+    B191,
+    B192,
+    B193,
+    B194,
+    B195,
+    B196,
+    B197,
+    B198,
+    B199,
+    B200,
+    B201,
+    B202,
+    B203,
+    B204,
+    B205,
+    B206,
+    B207,
+    B208,
+    B209,
+    B210,
+    B211,
+    B212,
+    B213,
+    B214,
+    B215,
+    B216,
+    B217,
+    B218,
+    B219,
+    B220,
+    B221,
+    B222,
+    B223,
+    B224,
+    B225,
+    B226,
+    B227,
+    B228,
+    B229,
+    B230,
+    B231,
+    B232,
+    B233,
+    B234,
+    B235,
+    B236,
+    B237,
+    B238,
+    B239,
+    B240,
+    B241,
+    B242,
+    B243,
+    B244,
+    B245,
+    B246,
+    B247,
+    B248,
+    B249,
+    B250,
+    B251,
+    B252,
+    B253,
+    B254,
+    B255,
+}
+
+// Every index of a `u8`, the most `bijection_enum_index!` can take
+bijection_enum_index!(
+    Byte,
+    u8,
+    [
+        B0, B1, B2, B3, B4, B5, B6, B7, B8, B9, B10, B11, B12, B13, B14, B15, B16, B17, B18, B19,
+        B20, B21, B22, B23, B24, B25, B26, B27, B28, B29, B30, B31, B32, B33, B34, B35, B36, B37,
+        B38, B39, B40, B41, B42, B43, B44, B45, B46, B47, B48, B49, B50, B51, B52, B53, B54, B55,
+        B56, B57, B58, B59, B60, B61, B62, B63, B64, B65, B66, B67, B68, B69, B70, B71, B72, B73,
+        B74, B75, B76, B77, B78, B79, B80, B81, B82, B83, B84, B85, B86, B87, B88, B89, B90, B91,
+        B92, B93, B94, B95, B96, B97, B98, B99, B100, B101, B102, B103, B104, B105, B106, B107,
+        B108, B109, B110, B111, B112, B113, B114, B115, B116, B117, B118, B119, B120, B121, B122,
+        B123, B124, B125, B126, B127, B128, B129, B130, B131, B132, B133, B134, B135, B136, B137,
+        B138, B139, B140, B141, B142, B143, B144, B145, B146, B147, B148, B149, B150, B151, B152,
+        B153, B154, B155, B156, B157, B158, B159, B160, B161, B162, B163, B164, B165, B166, B167,
+        B168, B169, B170, B171, B172, B173, B174, B175, B176, B177, B178, B179, B180, B181, B182,
+        B183, B184, B185, B186, B187, B188, B189, B190, B191, B192, B193, B194, B195, B196, B197,
+        B198, B199, B200, B201, B202, B203, B204, B205, B206, B207, B208, B209, B210, B211, B212,
+        B213, B214, B215, B216, B217, B218, B219, B220, B221, B222, B223, B224, B225, B226, B227,
+        B228, B229, B230, B231, B232, B233, B234, B235, B236, B237, B238, B239, B240, B241, B242,
+        B243, B244, B245, B246, B247, B248, B249, B250, B251, B252, B253, B254, B255,
+    ]
+);
+
+// Literals on one side, for every variant
+bijection!(Byte, u16, {
+    Byte::B0 => 1000,
+    Byte::B1 => 1001,
+    Byte::B2 => 1002,
+    Byte::B3 => 1003,
+    Byte::B4 => 1004,
+    Byte::B5 => 1005,
+    Byte::B6 => 1006,
+    Byte::B7 => 1007,
+    Byte::B8 => 1008,
+    Byte::B9 => 1009,
+    Byte::B10 => 1010,
+    Byte::B11 => 1011,
+    Byte::B12 => 1012,
+    Byte::B13 => 1013,
+    Byte::B14 => 1014,
+    Byte::B15 => 1015,
+    Byte::B16 => 1016,
+    Byte::B17 => 1017,
+    Byte::B18 => 1018,
+    Byte::B19 => 1019,
+    Byte::B20 => 1020,
+    Byte::B21 => 1021,
+    Byte::B22 => 1022,
+    Byte::B23 => 1023,
+    Byte::B24 => 1024,
+    Byte::B25 => 1025,
+    Byte::B26 => 1026,
+    Byte::B27 => 1027,
+    Byte::B28 => 1028,
+    Byte::B29 => 1029,
+    Byte::B30 => 1030,
+    Byte::B31 => 1031,
+    Byte::B32 => 1032,
+    Byte::B33 => 1033,
+    Byte::B34 => 1034,
+    Byte::B35 => 1035,
+    Byte::B36 => 1036,
+    Byte::B37 => 1037,
+    Byte::B38 => 1038,
+    Byte::B39 => 1039,
+    Byte::B40 => 1040,
+    Byte::B41 => 1041,
+    Byte::B42 => 1042,
+    Byte::B43 => 1043,
+    Byte::B44 => 1044,
+    Byte::B45 => 1045,
+    Byte::B46 => 1046,
+    Byte::B47 => 1047,
+    Byte::B48 => 1048,
+    Byte::B49 => 1049,
+    Byte::B50 => 1050,
+    Byte::B51 => 1051,
+    Byte::B52 => 1052,
+    Byte::B53 => 1053,
+    Byte::B54 => 1054,
+    Byte::B55 => 1055,
+    Byte::B56 => 1056,
+    Byte::B57 => 1057,
+    Byte::B58 => 1058,
+    Byte::B59 => 1059,
+    Byte::B60 => 1060,
+    Byte::B61 => 1061,
+    Byte::B62 => 1062,
+    Byte::B63 => 1063,
+    Byte::B64 => 1064,
+    Byte::B65 => 1065,
+    Byte::B66 => 1066,
+    Byte::B67 => 1067,
+    Byte::B68 => 1068,
+    Byte::B69 => 1069,
+    Byte::B70 => 1070,
+    Byte::B71 => 1071,
+    Byte::B72 => 1072,
+    Byte::B73 => 1073,
+    Byte::B74 => 1074,
+    Byte::B75 => 1075,
+    Byte::B76 => 1076,
+    Byte::B77 => 1077,
+    Byte::B78 => 1078,
+    Byte::B79 => 1079,
+    Byte::B80 => 1080,
+    Byte::B81 => 1081,
+    Byte::B82 => 1082,
+    Byte::B83 => 1083,
+    Byte::B84 => 1084,
+    Byte::B85 => 1085,
+    Byte::B86 => 1086,
+    Byte::B87 => 1087,
+    Byte::B88 => 1088,
+    Byte::B89 => 1089,
+    Byte::B90 => 1090,
+    Byte::B91 => 1091,
+    Byte::B92 => 1092,
+    Byte::B93 => 1093,
+    Byte::B94 => 1094,
+    Byte::B95 => 1095,
+    Byte::B96 => 1096,
+    Byte::B97 => 1097,
+    Byte::B98 => 1098,
+    Byte::B99 => 1099,
+    Byte::B100 => 1100,
+    Byte::B101 => 1101,
+    Byte::B102 => 1102,
+    Byte::B103 => 1103,
+    Byte::B104 => 1104,
+    Byte::B105 => 1105,
+    Byte::B106 => 1106,
+    Byte::B107 => 1107,
+    Byte::B108 => 1108,
+    Byte::B109 => 1109,
+    Byte::B110 => 1110,
+    Byte::B111 => 1111,
+    Byte::B112 => 1112,
+    Byte::B113 => 1113,
+    Byte::B114 => 1114,
+    Byte::B115 => 1115,
+    Byte::B116 => 1116,
+    Byte::B117 => 1117,
+    Byte::B118 => 1118,
+    Byte::B119 => 1119,
+    Byte::B120 => 1120,
+    Byte::B121 => 1121,
+    Byte::B122 => 1122,
+    Byte::B123 => 1123,
+    Byte::B124 => 1124,
+    Byte::B125 => 1125,
+    Byte::B126 => 1126,
+    Byte::B127 => 1127,
+    Byte::B128 => 1128,
+    Byte::B129 => 1129,
+    Byte::B130 => 1130,
+    Byte::B131 => 1131,
+    Byte::B132 => 1132,
+    Byte::B133 => 1133,
+    Byte::B134 => 1134,
+    Byte::B135 => 1135,
+    Byte::B136 => 1136,
+    Byte::B137 => 1137,
+    Byte::B138 => 1138,
+    Byte::B139 => 1139,
+    Byte::B140 => 1140,
+    Byte::B141 => 1141,
+    Byte::B142 => 1142,
+    Byte::B143 => 1143,
+    Byte::B144 => 1144,
+    Byte::B145 => 1145,
+    Byte::B146 => 1146,
+    Byte::B147 => 1147,
+    Byte::B148 => 1148,
+    Byte::B149 => 1149,
+    Byte::B150 => 1150,
+    Byte::B151 => 1151,
+    Byte::B152 => 1152,
+    Byte::B153 => 1153,
+    Byte::B154 => 1154,
+    Byte::B155 => 1155,
+    Byte::B156 => 1156,
+    Byte::B157 => 1157,
+    Byte::B158 => 1158,
+    Byte::B159 => 1159,
+    Byte::B160 => 1160,
+    Byte::B161 => 1161,
+    Byte::B162 => 1162,
+    Byte::B163 => 1163,
+    Byte::B164 => 1164,
+    Byte::B165 => 1165,
+    Byte::B166 => 1166,
+    Byte::B167 => 1167,
+    Byte::B168 => 1168,
+    Byte::B169 => 1169,
+    Byte::B170 => 1170,
+    Byte::B171 => 1171,
+    Byte::B172 => 1172,
+    Byte::B173 => 1173,
+    Byte::B174 => 1174,
+    Byte::B175 => 1175,
+    Byte::B176 => 1176,
+    Byte::B177 => 1177,
+    Byte::B178 => 1178,
+    Byte::B179 => 1179,
+    Byte::B180 => 1180,
+    Byte::B181 => 1181,
+    Byte::B182 => 1182,
+    Byte::B183 => 1183,
+    Byte::B184 => 1184,
+    Byte::B185 => 1185,
+    Byte::B186 => 1186,
+    Byte::B187 => 1187,
+    Byte::B188 => 1188,
+    Byte::B189 => 1189,
+    Byte::B190 => 1190,
+    Byte::B191 => 1191,
+    Byte::B192 => 1192,
+    Byte::B193 => 1193,
+    Byte::B194 => 1194,
+    Byte::B195 => 1195,
+    Byte::B196 => 1196,
+    Byte::B197 => 1197,
+    Byte::B198 => 1198,
+    Byte::B199 => 1199,
+    Byte::B200 => 1200,
+    Byte::B201 => 1201,
+    Byte::B202 => 1202,
+    Byte::B203 => 1203,
+    Byte::B204 => 1204,
+    Byte::B205 => 1205,
+    Byte::B206 => 1206,
+    Byte::B207 => 1207,
+    Byte::B208 => 1208,
+    Byte::B209 => 1209,
+    Byte::B210 => 1210,
+    Byte::B211 => 1211,
+    Byte::B212 => 1212,
+    Byte::B213 => 1213,
+    Byte::B214 => 1214,
+    Byte::B215 => 1215,
+    Byte::B216 => 1216,
+    Byte::B217 => 1217,
+    Byte::B218 => 1218,
+    Byte::B219 => 1219,
+    Byte::B220 => 1220,
+    Byte::B221 => 1221,
+    Byte::B222 => 1222,
+    Byte::B223 => 1223,
+    Byte::B224 => 1224,
+    Byte::B225 => 1225,
+    Byte::B226 => 1226,
+    Byte::B227 => 1227,
+    Byte::B228 => 1228,
+    Byte::B229 => 1229,
+    Byte::B230 => 1230,
+    Byte::B231 => 1231,
+    Byte::B232 => 1232,
+    Byte::B233 => 1233,
+    Byte::B234 => 1234,
+    Byte::B235 => 1235,
+    Byte::B236 => 1236,
+    Byte::B237 => 1237,
+    Byte::B238 => 1238,
+    Byte::B239 => 1239,
+    Byte::B240 => 1240,
+    Byte::B241 => 1241,
+    Byte::B242 => 1242,
+    Byte::B243 => 1243,
+    Byte::B244 => 1244,
+    Byte::B245 => 1245,
+    Byte::B246 => 1246,
+    Byte::B247 => 1247,
+    Byte::B248 => 1248,
+    Byte::B249 => 1249,
+    Byte::B250 => 1250,
+    Byte::B251 => 1251,
+    Byte::B252 => 1252,
+    Byte::B253 => 1253,
+    Byte::B254 => 1254,
+    Byte::B255 => 1255,
+});
+
+#[test]
+fn many_variants() {
+    assert_eq!(Bar::from(Foo::A0), Bar::X0);
+    assert_eq!(Bar::from(Foo::A1(1)), Bar::X1(1));
+    assert_eq!(Bar::from(Foo::A199(7)), Bar::X199(7));
+    assert_eq!(Foo::from(Bar::X0), Foo::A0);
+    assert_eq!(Foo::from(Bar::X198), Foo::A198);
+    assert_eq!(Foo::from(Bar::X199(7)), Foo::A199(7));
+}
+
+#[test]
+fn enum_index_256_variants() {
+    assert_eq!(u8::from(Byte::B0), 0);
+    assert_eq!(u8::from(Byte::B255), 255);
+    assert_eq!(Byte::try_from(128u8), Ok(Byte::B128));
+    assert_eq!(Byte::try_from(255u8), Ok(Byte::B255));
+}
+
+#[test]
+fn literal_block() {
+    assert_eq!(u16::from(Byte::B0), 1000);
+    assert_eq!(u16::from(Byte::B255), 1255);
+    assert_eq!(Byte::try_from(1128u16), Ok(Byte::B128));
+    assert_eq!(Byte::try_from(999u16), Err(999));
+}
@@ -0,0 +1,94 @@
+//! Expansion in a `#![no_std]` crate, without any prelude in scope.
+//! Every path in the generated code has to be spelled out from `::core` (or `$crate`) for this to compile.
+
+#![no_std]
+
+#[no_implicit_prelude]
+mod conversions {
+    #[derive(
+        ::core::fmt::Debug, ::core::cmp::PartialEq, ::core::clone::Clone, ::core::marker::Copy,
+    )]
+    pub enum Foo {
+        A,
+        B(u8),
+        C { value: i32 },
+    }
+
+    #[derive(
+        ::core::fmt::Debug, ::core::cmp::PartialEq, ::core::clone::Clone, ::core::marker::Copy,
+    )]
+    pub enum Bar {
+        X,
+        Y(u8),
+        Z(i32),
+    }
+
+    #[derive(::core::fmt::Debug, ::core::cmp::PartialEq)]
+    pub enum Baz {
+        One,
+        Two,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::cmp::PartialEq)]
+    pub enum Qux {
+        First,
+        Second,
+    }
+
+    ::biject_into::bijection!(#[by_ref] #[eq] Foo, Bar, {
+        Foo::A => Bar::X,
+        Foo::B(x) => Bar::Y(x),
+        Foo::C { value } => Bar::Z(value),
+    });
+
+    ::biject_into::try_bijection!(Baz, u8, {
+        Baz::One => 1,
+        Baz::Two => 2,
+    });
+
+    #[derive(::core::fmt::Debug, ::core::cmp::PartialEq)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    ::biject_into::bijection_chain!(Qux, Level, bool, {
+        a_b: {
+            Qux::First => Level::Low,
+            Qux::Second => Level::High,
+        },
+        b_c: {
+            Level::Low => false,
+            Level::High => true,
+        },
+    });
+
+    ::biject_into::str_bijection!(Qux, {
+        Qux::First => "first",
+        Qux::Second => "second",
+    });
+}
+
+use conversions::{Bar, Baz, Foo, Qux};
+
+#[test]
+fn no_prelude_bijection() {
+    assert_eq!(Bar::from(Foo::B(3)), Bar::Y(3));
+    assert_eq!(Foo::from(Bar::Z(-5)), Foo::C { value: -5 });
+    assert_eq!(Bar::from(&Foo::A), Bar::X);
+    assert!(Foo::B(3) == Bar::Y(3));
+}
+
+#[test]
+fn no_prelude_try_bijection() {
+    assert_eq!(u8::try_from(Baz::Two), Ok(2));
+    assert_eq!(Baz::try_from(1), Ok(Baz::One));
+    assert!(Baz::try_from(3).is_err());
+}
+
+#[test]
+fn no_prelude_chain_and_str() {
+    assert!(bool::from(Qux::Second));
+    assert_eq!(Qux::from(false), Qux::First);
+    assert_eq!("second".parse::<Qux>(), Ok(Qux::Second));
+}
@@ -0,0 +1,27 @@
+//! Roundtrip tests of two bijections in the same module, each generated under its own name.
+
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Foo {
+    A,
+    B(u8),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Bar {
+    X,
+    Y(u8),
+}
+
+bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+    Foo::B(n) => Bar::Y(n),
+} test_samples(foo_bar_roundtrip) = [Foo::A, Foo::B(0), Foo::B(u8::MAX)]);
+
+#[derive(Debug, PartialEq, Clone)]
+struct Meters(u32);
+
+bijection!(Meters, u32, {
+    Meters(m) => m,
+} test_samples(meters_roundtrip) = [Meters(0), Meters(5)]);
@@ -0,0 +1,15 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+bijection!(#[also_tryfrom(MyError)] Foo, Bar, {
+    Foo::A => Bar::X,
+});
+
+fn main() {}
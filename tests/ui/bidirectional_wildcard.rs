@@ -0,0 +1,18 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B(u8),
+}
+
+enum Bar {
+    X,
+    Y(u8),
+}
+
+bijection!(Foo, Bar, {
+    _ <=> Bar::X,
+    Foo::B(x) => Bar::Y(x),
+});
+
+fn main() {}
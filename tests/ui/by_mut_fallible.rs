@@ -0,0 +1,15 @@
+use biject_into::bijection;
+
+#[derive(Default)]
+enum Foo {
+    #[default]
+    A,
+    B,
+}
+
+bijection!(#[by_mut] Foo, u8, {
+    Foo::A => 0,
+    Foo::B => 1,
+});
+
+fn main() {}
@@ -0,0 +1,18 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B(u8),
+}
+
+enum Bar {
+    X,
+    Y(u8),
+}
+
+bijection!(#[const(to_bar, to_foo)] Foo, Bar, {
+    Foo::A => Bar::X,
+    Foo::B(b) => Bar::Y(b),
+});
+
+fn main() {}
@@ -0,0 +1,12 @@
+use biject_into::bijection;
+
+struct Foo(i32);
+struct Bar(i32);
+
+bijection!(Foo, Bar, {
+    Foo(0) => Bar(0),
+    Foo(1) => Bar(0),
+    Foo(x) => Bar(x),
+});
+
+fn main() {}
@@ -0,0 +1,15 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+enum Bar {
+    X,
+    Y,
+}
+
+bijection!(Foo, Bar, {});
+
+fn main() {}
@@ -0,0 +1,22 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+struct Flags(u8);
+
+impl Flags {
+    const A: Flags = Flags(1);
+}
+
+#[derive(Debug, PartialEq)]
+enum Flag {
+    A,
+}
+
+// Unknown values are converted into the error type, which needs `From<Flags>`
+struct MyError;
+
+bijection!(Flag, Flags, error = MyError, {
+    Flag::A => Flags::A,
+});
+
+fn main() {}
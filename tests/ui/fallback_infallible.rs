@@ -0,0 +1,23 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+fn foo_to_bar(_: Foo) -> Result<Bar, Foo> {
+    Ok(Bar::X)
+}
+
+fn bar_to_foo(_: Bar) -> Result<Foo, Bar> {
+    Ok(Foo::A)
+}
+
+bijection!(#[fallback(foo_to_bar, bar_to_foo)] Foo, Bar, {
+    Foo::A => Bar::X,
+});
+
+fn main() {}
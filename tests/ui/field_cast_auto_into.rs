@@ -0,0 +1,15 @@
+use biject_into::bijection;
+
+enum Foo {
+    N(u32, bool),
+}
+
+enum Bar {
+    N(u64, bool),
+}
+
+bijection!(#[auto_into] Foo, Bar, {
+    Foo::N(x as u64, y) => Bar::N(x, y),
+});
+
+fn main() {}
@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+enum Foo {
+    Val(i32),
+}
+
+enum Bar {
+    Positive(i32),
+    Other(i32),
+}
+
+bijection!(Foo, Bar, {
+    Foo::Val(n) if n > 0 => Bar::Positive(n),
+    Foo::Val(n) => Bar::Other(n),
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+bijection!(Foo, Foo, {
+    Foo::A => Foo::B,
+    Foo::B => Foo::A,
+});
+
+fn main() {}
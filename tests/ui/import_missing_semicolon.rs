@@ -0,0 +1,15 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+bijection!(Foo, Bar, {
+    use Foo::*
+});
+
+fn main() {}
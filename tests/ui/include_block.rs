@@ -0,0 +1,13 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+bijection!(Foo, Bar, include!("foo_bar.in"));
+
+fn main() {}
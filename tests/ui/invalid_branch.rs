@@ -0,0 +1,21 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+    C,
+}
+
+enum Bar {
+    X,
+    Y,
+    Z,
+}
+
+bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+    Foo::B = Bar::Y,
+    Foo::C => Bar::Z,
+});
+
+fn main() {}
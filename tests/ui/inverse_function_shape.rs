@@ -0,0 +1,25 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq)]
+enum Length {
+    Meters(u32),
+}
+
+#[derive(Debug, PartialEq)]
+enum Size {
+    Millimeters(u32),
+}
+
+fn meters_to_mm(m: u32) -> u32 {
+    m * 1000
+}
+
+fn mm_to_meters(mm: u32) -> u32 {
+    mm / 1000
+}
+
+bijection!(Length, Size, {
+    Length::Meters(m) => Size::Millimeters(meters_to_mm(m) + 1) <=> mm_to_meters,
+});
+
+fn main() {}
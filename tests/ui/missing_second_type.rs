@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq)]
+enum Foo {
+    A,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bar {
+    X,
+}
+
+bijection!(Foo, {
+    Foo::A => Bar::X,
+});
+
+fn main() {}
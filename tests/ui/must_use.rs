@@ -0,0 +1,19 @@
+#![deny(unused_must_use)]
+
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+bijection!(#[must_use] Foo, Bar, {
+    Foo::A => Bar::X,
+});
+
+fn main() {
+    Bar::from(Foo::A);
+}
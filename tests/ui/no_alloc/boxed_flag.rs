@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+}
+
+enum Bar {
+    X,
+}
+
+bijection!(#[boxed] Foo, Bar, {
+    Foo::A => Bar::X,
+});
+
+fn main() {
+    let _ = Bar::from(Foo::A);
+}
@@ -0,0 +1,12 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq)]
+enum Enabled {
+    Yes,
+}
+
+bijection!(Enabled, bool, {
+    Enabled::Yes => true,
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use biject_into::bijection;
+
+enum Grade {
+    Pass,
+    Fail,
+}
+
+bijection!(Grade, f64, {
+    Grade::Pass => 1.0,
+    Grade::Fail => 0.0,
+});
+
+fn main() {}
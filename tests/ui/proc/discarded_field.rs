@@ -0,0 +1,18 @@
+use biject_into::checked;
+
+enum Foo {
+    Known(u8),
+    Unknown(u32),
+}
+
+enum Bar {
+    Known(u8),
+    Unknown,
+}
+
+checked::bijection!(Foo, Bar, {
+    Foo::Known(n) => Bar::Known(n),
+    Foo::Unknown(_) => Bar::Unknown,
+});
+
+fn main() {}
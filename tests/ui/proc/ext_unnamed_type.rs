@@ -0,0 +1,13 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+bijection!(#[ext] Foo, (bool,), {
+    Foo::A => (false,),
+    Foo::B => (true,),
+});
+
+fn main() {}
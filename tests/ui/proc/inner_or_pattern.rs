@@ -0,0 +1,11 @@
+use biject_into::checked;
+
+struct Foo(i32);
+struct Bar(i32);
+
+checked::bijection!(Foo, Bar, {
+    Foo(2 | 3) => Bar(1),
+    Foo(x) => Bar(x),
+});
+
+fn main() {}
@@ -0,0 +1,26 @@
+use biject_into::checked;
+
+#[derive(Debug, PartialEq)]
+enum Foo {
+    A,
+    B,
+    C,
+    D,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bar {
+    X,
+    Y,
+}
+
+checked::bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+    forward_only { Foo::C | Foo::D => Bar::X }
+    Foo::B => Bar::Y,
+});
+
+fn main() {
+    assert_eq!(Bar::from(Foo::D), Bar::X);
+    assert_eq!(Foo::from(Bar::Y), Foo::B);
+}
@@ -0,0 +1,23 @@
+use biject_into::checked;
+
+#[derive(Debug, PartialEq)]
+enum Foo {
+    A,
+    B,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bar {
+    X,
+    Y,
+    Z,
+}
+
+checked::bijection!(Foo <= Bar, {
+    Foo::A => Bar::X,
+    Foo::B => Bar::Y | Bar::Z,
+});
+
+fn main() {
+    assert_eq!(Foo::from(Bar::Z), Foo::B);
+}
@@ -0,0 +1,14 @@
+use biject_into::bijection;
+
+#[repr(u8)]
+enum Op {
+    Add = 1,
+    Sub = 2,
+}
+
+bijection!(#[repr_cast] Op, u8, {
+    Op::Add => 1,
+    Op::Sub => 3,
+});
+
+fn main() {}
@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+#[derive(Default)]
+struct Foo {
+    x: u8,
+    y: u8,
+}
+
+enum Bar {
+    X(u8),
+}
+
+bijection!(Foo, Bar, {
+    Foo { x, .. } => Bar::X(x),
+});
+
+fn main() {}
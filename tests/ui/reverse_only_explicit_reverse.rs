@@ -0,0 +1,18 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+enum Bar {
+    X,
+    Y,
+}
+
+bijection!(Foo <= Bar, {
+    Foo::A => Bar::X; reverse Bar::X => Foo::A,
+    Foo::B => Bar::Y,
+});
+
+fn main() {}
@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq)]
+enum Foo {
+    A,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bar {
+    X,
+}
+
+bijection!(Foo => Bar, {
+    Foo::A => Bar::X,
+} test_samples(foo_bar_roundtrip) = [Foo::A]);
+
+fn main() {}
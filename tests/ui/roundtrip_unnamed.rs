@@ -0,0 +1,17 @@
+use biject_into::bijection;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Foo {
+    A,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Bar {
+    X,
+}
+
+bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+} test_samples = [Foo::A]);
+
+fn main() {}
@@ -0,0 +1,20 @@
+use biject_into::try_bijection;
+
+enum Foo {
+    A,
+    B,
+    C,
+}
+
+enum Bar {
+    X,
+    Y,
+    Z,
+}
+
+try_bijection!(#[same_arity(3)] Foo, Bar, {
+    Foo::A => Bar::X,
+    Foo::B => Bar::Y,
+});
+
+fn main() {}
@@ -0,0 +1,15 @@
+use biject_into::bijection_same_names;
+
+enum Foo {
+    A,
+    B(u8),
+}
+
+enum Bar {
+    A,
+    B(u8),
+}
+
+bijection_same_names!(Foo, Bar, [A, B(x)]);
+
+fn main() {}
@@ -0,0 +1,18 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+enum Bar {
+    X,
+    Y,
+}
+
+bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+    Foo::B : Bar::Y,
+});
+
+fn main() {}
@@ -0,0 +1,13 @@
+use biject_into::bijection;
+
+struct Foo(u8);
+
+struct Bar(u8);
+
+bijection!(#[strict] Foo, Bar, {
+    Foo(0) => Bar(0),
+    Foo(0) => Bar(1),
+    Foo(x) => Bar(x),
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+use biject_into::bijection;
+
+#[derive(Clone)]
+enum Code {
+    Ok,
+    Other(u8),
+}
+
+bijection!(#[table] Code, u8, {
+    Code::Ok => 0,
+    Code::Other(code) => code,
+});
+
+fn main() {}
@@ -0,0 +1,16 @@
+#![deny(unreachable_patterns)]
+
+use biject_into::bijection;
+
+struct Foo(i32);
+struct Bar(i32);
+
+// Neither is a textual duplicate, but both are unreachable in their `match`
+bijection!(Foo, Bar, {
+    Foo(0) => Bar(0),
+    Foo(1) => Bar(0x0),
+    Foo(1) => Bar(1),
+    Foo(x) => Bar(x),
+});
+
+fn main() {}
@@ -0,0 +1,16 @@
+#![deny(unreachable_patterns)]
+
+use biject_into::bijection;
+
+struct Foo(i32);
+struct Bar(i32);
+
+// Branches separated by `;`, and explicit reverses, keep their patterns as written as well
+bijection!(Foo, Bar, {
+    Foo(0) => Bar(0);
+    Foo(1) => Bar(0x0);
+    Foo(2) => Bar(2); reverse Bar(00) => Foo(2);
+    Foo(x) => Bar(x)
+});
+
+fn main() {}
@@ -0,0 +1,14 @@
+#![deny(unreachable_patterns)]
+
+use biject_into::bijection;
+
+struct Foo(u8);
+struct Bar(u8);
+
+// Passes with `#[allow_unreachable]` (see `tests/allow_unreachable.rs`)
+bijection!(Foo, Bar, {
+    Foo(x) => Bar(x),
+    Foo(0) => Bar(0); reverse Bar(0) => Foo(0),
+});
+
+fn main() {}
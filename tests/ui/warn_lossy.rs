@@ -0,0 +1,21 @@
+#![deny(deprecated)]
+
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+    Reserved,
+}
+
+enum Bar {
+    X,
+    Other,
+}
+
+bijection!(#[warn_lossy] Foo, Bar, {
+    Foo::A => Bar::X,
+    _ => Bar::Other; reverse Bar::Other => Foo::B,
+});
+
+fn main() {}
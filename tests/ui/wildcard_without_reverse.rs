@@ -0,0 +1,18 @@
+use biject_into::bijection;
+
+enum Foo {
+    A,
+    B,
+}
+
+enum Bar {
+    X,
+    Other,
+}
+
+bijection!(Foo, Bar, {
+    Foo::A => Bar::X,
+    _ => Bar::Other,
+});
+
+fn main() {}